@@ -1,30 +1,77 @@
-use crate::{build_run, build_test};
-fn part1() -> i64 {
-    let [mut l1, mut l2] = get_list();
-    l1.sort();
-    l2.sort();
-    l1.into_iter().zip(l2).map(|(v1, v2)| (v1 - v2).abs()).sum()
+use crate::{
+    build_checks, build_run, build_test,
+    utilities::{
+        datatypes::NumberList,
+        input::{Input, ParseError},
+        rng::Rng,
+        schema::{LineShape, Schema},
+        Counter,
+    },
+};
+fn part1(input: &str) -> i64 {
+    let [l1, l2] = get_list(input).unwrap();
+    l1.sorted()
+        .iter()
+        .zip(l2.sorted().iter())
+        .map(|(v1, v2)| (v1 - v2).abs())
+        .sum()
 }
-fn part2() -> i64 {
-    let [l1, l2] = get_list();
-    let mut numbers_right = vec![0; 100_000];
-    for number in l2 {
-        numbers_right[number as usize] += 1;
+fn part2(input: &str) -> i64 {
+    let [l1, l2] = get_list(input).unwrap();
+    let numbers_right: Counter<i64, i64> = l2.frequencies();
+    l1.iter().map(|v| v * numbers_right.get(v)).sum()
+}
+const INPUT: &str = include_str!("input.txt");
+/// Two columns of location IDs.
+pub const SCHEMA: Schema = Schema::Lines {
+    shape: LineShape::Numbers {
+        separator: " ",
+        count: 2..=2,
+    },
+    count: 1..=usize::MAX,
+};
+fn get_list(input: &str) -> Result<[NumberList<i64>; 2], ParseError> {
+    let mut l1 = NumberList::new(Vec::new());
+    let mut l2 = NumberList::new(Vec::new());
+    let pairs = input.parse_lines_with(|line| -> Result<(i64, i64), ParseError> {
+        match line.parse::<NumberList<i64>>()?[..] {
+            [a, b] => Ok((a, b)),
+            _ => Err(ParseError::Missing("a pair of location IDs")),
+        }
+    })?;
+    for (a, b) in pairs {
+        l1.push(a);
+        l2.push(b);
     }
-    l1.into_iter().map(|v| v * numbers_right[v as usize]).sum()
+    Ok([l1, l2])
 }
-fn get_list() -> [Vec<i64>; 2] {
-    let mut l1 = Vec::new();
-    let mut l2 = Vec::new();
-    let input = include_str!("input.txt");
-    for line in input.trim().lines() {
-        let mut splited = line.trim().splitn(2, "   ");
-        let num1 = splited.next().unwrap().parse::<i64>().unwrap();
-        let num2 = splited.next().unwrap().parse::<i64>().unwrap();
-        l1.push(num1);
-        l2.push(num2);
+/// Generates `size` pairs of location IDs. Half of the right column repeats IDs of the left column,
+/// so the similarity score is not zero.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let left: Vec<usize> = (0..size).map(|_| rng.range(10000..100000)).collect();
+    let mut result = String::new();
+    for l in &left {
+        let r = match rng.chance(0.5) {
+            true => *rng.choose(&left),
+            false => rng.range(10000..100000),
+        };
+        result += &format!("{l}   {r}\n");
     }
-    [l1, l2]
+    result
+}
+
+pub fn fuzz(input: &str) {
+    let _ = get_list(input);
 }
+
 build_run!(1, part1, part2);
 build_test!(part1: 2264607, part2: 19457120);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 1..30,
+    reference: 1..30, Shrink::Lines;
+    part1 => reference::part1,
+    part2 => reference::part2,
+);
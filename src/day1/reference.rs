@@ -0,0 +1,30 @@
+//! A naive solution to check the optimized one against.
+
+fn columns(input: &str) -> (Vec<i64>, Vec<i64>) {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut numbers = line.split_whitespace().map(|n| n.parse::<i64>().unwrap());
+            (numbers.next().unwrap(), numbers.next().unwrap())
+        })
+        .unzip()
+}
+
+pub(super) fn part1(input: &str) -> i64 {
+    let (mut left, mut right) = columns(input);
+    let mut result = 0;
+    while !left.is_empty() {
+        let l = left.remove((0..left.len()).min_by_key(|&i| left[i]).unwrap());
+        let r = right.remove((0..right.len()).min_by_key(|&i| right[i]).unwrap());
+        result += (l - r).abs();
+    }
+    result
+}
+
+pub(super) fn part2(input: &str) -> i64 {
+    let (left, right) = columns(input);
+    left.iter()
+        .map(|l| l * right.iter().filter(|&r| r == l).count() as i64)
+        .sum()
+}
@@ -1,43 +1,48 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    num::ParseIntError,
-    ops::Index,
-    str::FromStr,
-};
+use std::{collections::VecDeque, ops::Index, str::FromStr};
 
 use crate::{
-    build_run, build_test,
-    utilities::{IteratorToVec, ParseTrimmedLines, StringCharVec},
+    build_checks, build_run, build_test,
+    utilities::{
+        grid::orthogonal_neighbours,
+        input::{Input, ParseError},
+        rng::Rng,
+        schema::{Alphabet, GridSize, Schema},
+        ArrayVec, FastHashSet, IteratorToVec, StringCharVec,
+    },
 };
 
-fn part1() -> usize {
-    let map = load_map();
-    map.get_trailheads()
-        .into_iter()
-        .map(|point| map.get_score(point))
-        .sum()
+fn part1(input: &str) -> usize {
+    load_map(input).unwrap().total_score()
 }
 
-fn part2() -> usize {
-    let map = load_map();
-    map.get_trailheads()
-        .into_iter()
-        .map(|point| map.get_rating(point))
-        .sum()
+fn part2(input: &str) -> usize {
+    load_map(input).unwrap().total_rating()
 }
 
-struct Map<const SIZE: usize> {
-    data: [[u8; SIZE]; SIZE],
+struct Map {
+    data: Vec<Vec<u8>>,
 }
-impl<const SIZE: usize> Map<SIZE> {
+impl Map {
+    fn total_score(&self) -> usize {
+        self.get_trailheads()
+            .into_iter()
+            .map(|point| self.get_score(point))
+            .sum()
+    }
+    fn total_rating(&self) -> usize {
+        self.get_trailheads()
+            .into_iter()
+            .map(|point| self.get_rating(point))
+            .sum()
+    }
     fn get_trailheads(&self) -> Vec<(usize, usize)> {
         self.data
-            .into_iter()
+            .iter()
             .enumerate()
             .flat_map(|(i, row)| {
-                row.into_iter()
+                row.iter()
                     .enumerate()
-                    .filter(|&(_, value)| value == 0)
+                    .filter(|&(_, &value)| value == 0)
                     .map(move |(j, _)| (i, j))
             })
             .to_vec()
@@ -45,17 +50,13 @@ impl<const SIZE: usize> Map<SIZE> {
     fn get_score(&self, coord: (usize, usize)) -> usize {
         let mut to_search = VecDeque::new();
         to_search.push_back(coord);
-        let mut found = HashSet::new();
+        let mut found = FastHashSet::default();
         while let Some(point) = to_search.pop_front() {
             if self[point] == 9 {
                 found.insert(point);
                 continue;
             }
-            for point in self.sourrounding_higher(point) {
-                if let Some(point) = point {
-                    to_search.push_back(point);
-                }
-            }
+            to_search.extend(self.sourrounding_higher(point));
         }
         found.len()
     }
@@ -68,34 +69,21 @@ impl<const SIZE: usize> Map<SIZE> {
                 found += 1;
                 continue;
             }
-            for point in self.sourrounding_higher(point) {
-                if let Some(point) = point {
-                    to_search.push_back(point);
-                }
-            }
+            to_search.extend(self.sourrounding_higher(point));
         }
         found
     }
-    fn sourrounding_higher(&self, coord: (usize, usize)) -> [Option<(usize, usize)>; 4] {
-        let mut result = [None; 4];
+    fn sourrounding_higher(&self, coord: (usize, usize)) -> ArrayVec<(usize, usize), 4> {
         let value = self[coord];
-        if coord.0 > 0 && self.data[coord.0 - 1][coord.1] == value + 1 {
-            result[0] = Some((coord.0 - 1, coord.1));
-        }
-        if coord.1 > 0 && self.data[coord.0][coord.1 - 1] == value + 1 {
-            result[1] = Some((coord.0, coord.1 - 1));
-        }
-        if coord.0 < SIZE - 1 && self.data[coord.0 + 1][coord.1] == value + 1 {
-            result[2] = Some((coord.0 + 1, coord.1));
-        }
-        if coord.1 < SIZE - 1 && self.data[coord.0][coord.1 + 1] == value + 1 {
-            result[3] = Some((coord.0, coord.1 + 1));
-        }
-        result
+        let size = self.data.len();
+        orthogonal_neighbours(coord, size, size)
+            .into_iter()
+            .filter(|&neighbour| self[neighbour] == value + 1)
+            .collect()
     }
 }
 
-impl<const SIZE: usize> Index<(usize, usize)> for Map<SIZE> {
+impl Index<(usize, usize)> for Map {
     type Output = u8;
 
     #[inline(always)]
@@ -104,48 +92,91 @@ impl<const SIZE: usize> Index<(usize, usize)> for Map<SIZE> {
     }
 }
 
-const STR: &str = include_str!("input.txt");
-const STR_LINES: usize = STR.len().isqrt();
-struct Line<const SIZE: usize> {
-    data: [u8; SIZE],
+const INPUT: &str = include_str!("input.txt");
+/// The topographic map of heights.
+pub const SCHEMA: Schema = Schema::Grid {
+    size: GridSize::Square,
+    alphabet: Alphabet::DIGITS,
+};
+struct Line {
+    data: Vec<u8>,
 }
-#[derive(Debug)]
-#[allow(dead_code)]
-enum LineError {
-    ParseIntError(ParseIntError),
-    Other(&'static str),
+impl FromStr for Line {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars = s.char_vec();
+        if let Some(&c) = chars.iter().find(|c| !c.is_ascii_digit()) {
+            return Err(ParseError::UnexpectedChar(c));
+        }
+        let data = chars.into_iter().map(|c| c as u8 - b'0').collect();
+        Ok(Self { data })
+    }
 }
-impl From<ParseIntError> for LineError {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseIntError(value)
+fn load_map(input: &str) -> Result<Map, ParseError> {
+    let lines: Vec<Line> = input.parse_lines()?;
+    let size = lines.len();
+    if let Some(line) = lines.iter().find(|line| line.data.len() != size) {
+        return Err(ParseError::Dimensions {
+            expected: (size, size),
+            found: (size, line.data.len()),
+        });
     }
+    let data = lines.into_iter().map(|line| line.data).to_vec();
+    Ok(Map { data })
 }
-impl<const SIZE: usize> FromStr for Line<SIZE> {
-    type Err = LineError;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let chars: [char; SIZE] = s
-            .char_vec()
-            .try_into()
-            .map_err(|_| LineError::Other("Could not convert to the correct sized char array"))?;
-        let data: [u8; SIZE] = chars
-            .map(|c| c.to_digit(10))
-            .transpose()
-            .ok_or(LineError::Other("Could not Parse an Char"))?
-            .map(|v| v as u8);
-        Ok(Self { data })
+/// Generates a `size` x `size` map of random heights with up to `size / 2` planted hiking trails,
+/// each climbing from `0` to `9` in orthogonal steps without crossing itself.
+///
+/// Generates a `size` x `size` map of random heights with up to `size / 2` planted hiking trails,
+/// each climbing from `0` to `9` in orthogonal steps without crossing itself.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut grid: Vec<Vec<u8>> = (0..size)
+        .map(|_| (0..size).map(|_| rng.range(0..10) as u8).collect())
+        .collect();
+    for _ in 0..size / 2 {
+        let mut trail: ArrayVec<(usize, usize), 10> = ArrayVec::new();
+        trail.push((rng.range(0..size), rng.range(0..size)));
+        while !trail.is_full() {
+            let free: ArrayVec<_, 4> = orthogonal_neighbours(trail[trail.len() - 1], size, size)
+                .into_iter()
+                .filter(|pos| !trail.contains(pos))
+                .collect();
+            if free.is_empty() {
+                break;
+            }
+            trail.push(*rng.choose(&free));
+        }
+        if trail.is_full() {
+            for (height, &(row, column)) in trail.iter().enumerate() {
+                grid[row][column] = height as u8;
+            }
+        }
     }
+    grid.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|h| (b'0' + h) as char)
+                .chain(['\n'])
+                .collect::<String>()
+        })
+        .collect()
 }
-fn load_map() -> Map<STR_LINES> {
-    let test: Vec<Line<STR_LINES>> = STR.parse_trimmed_lines().unwrap();
-    let data: [[u8; STR_LINES]; STR_LINES] = test
-        .into_iter()
-        .map(|line| line.data)
-        .to_vec()
-        .try_into()
-        .unwrap();
-    Map { data }
+
+pub fn fuzz(input: &str) {
+    let _ = load_map(input);
+    let _ = input.parse::<Line>();
 }
 
 build_run!(10, part1, part2);
 build_test!(part1: 778, part2: 1925);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 1..16,
+    reference: 4..16, Shrink::Grid { filler: '5' };
+    part1 => reference::part1,
+    part2 => reference::part2,
+);
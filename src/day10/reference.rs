@@ -0,0 +1,59 @@
+//! A naive solution to check the optimized one against.
+
+use std::collections::HashSet;
+
+fn heights(input: &str) -> Vec<Vec<u32>> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.trim()
+                .chars()
+                .map(|c| c.to_digit(10).unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+/// Collects the end of every hiking trail starting at `(row, column)`, once per trail.
+fn trail_ends(map: &[Vec<u32>], (row, column): (usize, usize), ends: &mut Vec<(usize, usize)>) {
+    let height = map[row][column];
+    if height == 9 {
+        ends.push((row, column));
+        return;
+    }
+    let neighbours = [
+        (row.wrapping_sub(1), column),
+        (row + 1, column),
+        (row, column.wrapping_sub(1)),
+        (row, column + 1),
+    ];
+    for (r, c) in neighbours {
+        if map.get(r).and_then(|line| line.get(c)) == Some(&(height + 1)) {
+            trail_ends(map, (r, c), ends);
+        }
+    }
+}
+
+fn sum_trailheads(input: &str, value: fn(Vec<(usize, usize)>) -> usize) -> usize {
+    let map = heights(input);
+    let mut result = 0;
+    for row in 0..map.len() {
+        for column in 0..map[row].len() {
+            if map[row][column] == 0 {
+                let mut ends = Vec::new();
+                trail_ends(&map, (row, column), &mut ends);
+                result += value(ends);
+            }
+        }
+    }
+    result
+}
+
+pub(super) fn part1(input: &str) -> usize {
+    sum_trailheads(input, |ends| ends.into_iter().collect::<HashSet<_>>().len())
+}
+
+pub(super) fn part2(input: &str) -> usize {
+    sum_trailheads(input, |ends| ends.len())
+}
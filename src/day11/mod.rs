@@ -1,19 +1,27 @@
-use std::{collections::HashMap, hash::Hash, str::FromStr};
+use std::{hash::Hash, str::FromStr};
 
 use crate::{
-    build_run, build_test,
-    utilities::{CollectIntoResult, DigitCount, IsEven, MapParse},
+    build_checks, build_run, build_test,
+    utilities::{
+        digits::Digits,
+        input::ParseError,
+        rng::Rng,
+        schema::{LineShape, Schema},
+        CollectIntoResult, Counter, IsEven, MapParse,
+    },
 };
 
-fn part1() -> u128 {
-    let mut stones = load_stones();
-    stones.blinkn(25);
-    stones.count_stones()
+fn part1(input: &str) -> u128 {
+    count_after_blinks(input, 25)
+}
+
+fn part2(input: &str) -> u128 {
+    count_after_blinks(input, 75)
 }
 
-fn part2() -> u128 {
-    let mut stones = load_stones();
-    stones.blinkn(75);
+fn count_after_blinks(input: &str, blinks: usize) -> u128 {
+    let mut stones = load_stones(input).unwrap();
+    stones.blinkn(blinks);
     stones.count_stones()
 }
 
@@ -38,12 +46,11 @@ impl Stone {
             self.value = 1;
             return None;
         }
-        let digit_count = self.value.digit_count() as u32;
+        let digit_count = self.value.digit_count(10);
         if digit_count.is_even() {
-            let ten_pow = 10u64.pow(digit_count / 2);
-            let new_value = self.value % ten_pow;
-            self.value /= ten_pow;
-            return Some(Self { value: new_value });
+            let (high, low) = self.value.split_at_digit(digit_count / 2, 10);
+            self.value = high;
+            return Some(Self { value: low });
         }
         self.value *= 2024;
         None
@@ -60,50 +67,66 @@ impl FromStr for Stone {
 }
 
 struct StoneCollection {
-    stones: Vec<(Stone, u64)>,
+    stones: Counter<Stone, u64>,
 }
 impl StoneCollection {
     // this function ignores to contain the order because it is not needed to solve the problem
     fn blink(&mut self) {
-        for i in 0..self.stones.len() {
-            let (stone, amount) = &mut self.stones[i];
-            let amount = *amount;
-            if let Some(stone) = stone.blink() {
-                self.stones.push((stone, amount));
-                continue;
+        let mut stones = Counter::new();
+        for (stone, amount) in self.stones.entries() {
+            let mut stone = *stone;
+            if let Some(new_stone) = stone.blink() {
+                stones.add(new_stone, amount);
             }
+            stones.add(stone, amount);
         }
-        self.merge();
+        self.stones = stones;
     }
     fn blinkn(&mut self, n: usize) {
         for _ in 0..n {
             self.blink();
         }
     }
-    fn merge(&mut self) {
-        let mut data = HashMap::new();
-        for (stone, amount) in &self.stones {
-            *data.entry(stone).or_insert(0) += amount;
-        }
-        self.stones = data.into_iter().map(|(k, v)| (*k, v)).collect();
-    }
     fn count_stones(&self) -> u128 {
-        self.stones.iter().map(|&(_, amount)| amount as u128).sum()
+        self.stones.total()
     }
 }
 
-const STR: &str = include_str!("input.txt");
-fn load_stones() -> StoneCollection {
-    StoneCollection {
-        stones: STR
-            .trim()
-            .split_whitespace()
-            .parse()
-            .map(|stone_res| stone_res.map(|stone| (stone, 1)))
-            .collect_result()
-            .unwrap(),
-    }
+const INPUT: &str = include_str!("input.txt");
+/// The numbers engraved on the stones, on a single line.
+pub const SCHEMA: Schema = Schema::Lines {
+    shape: LineShape::Numbers {
+        separator: " ",
+        count: 1..=usize::MAX,
+    },
+    count: 1..=1,
+};
+fn load_stones(input: &str) -> Result<StoneCollection, ParseError> {
+    Ok(StoneCollection {
+        stones: input.trim().split_whitespace().parse().collect_result()?,
+    })
+}
+
+/// Generates a line of `size` stones with values below one million.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let stones: Vec<String> = (0..size)
+        .map(|_| rng.range(0..1_000_000).to_string())
+        .collect();
+    stones.join(" ") + "\n"
+}
+
+pub fn fuzz(input: &str) {
+    let _ = load_stones(input);
+    let _ = input.parse::<Stone>();
 }
 
 build_run!(11, part1, part2);
 build_test!(part1: 189547, part2: 224577979481346);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 1..20,
+    reference: 1..4, Shrink::Lines;
+    |input| count_after_blinks(input, 12) => |input| reference::count_after_blinks(input, 12),
+);
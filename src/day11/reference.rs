@@ -0,0 +1,26 @@
+//! A naive solution to check the optimized one against.
+
+/// Blinks `blinks` times at the stones kept in order.
+pub(super) fn count_after_blinks(input: &str, blinks: usize) -> u128 {
+    let mut stones: Vec<u64> = input
+        .split_whitespace()
+        .map(|n| n.parse().unwrap())
+        .collect();
+    for _ in 0..blinks {
+        stones = stones
+            .into_iter()
+            .flat_map(|stone| {
+                let digits = stone.to_string();
+                if stone == 0 {
+                    vec![1]
+                } else if digits.len() % 2 == 0 {
+                    let (high, low) = digits.split_at(digits.len() / 2);
+                    vec![high.parse().unwrap(), low.parse().unwrap()]
+                } else {
+                    vec![stone * 2024]
+                }
+            })
+            .collect();
+    }
+    stones.len() as u128
+}
@@ -1,62 +1,81 @@
-use std::{
-    array::TryFromSliceError,
-    collections::{HashMap, HashSet, VecDeque},
-    fmt::{Debug, Display},
-    num::ParseIntError,
-    ops::Index,
-    str::FromStr,
-};
+use std::{ops::Index, str::FromStr};
 
 use crate::{
-    build_run, build_test,
-    utilities::{CollectIntoResult, IteratorToVec, IteratorTrimmedLines, StringCharVec},
+    build_checks, build_run, build_test,
+    utilities::{
+        dsu::grid_components,
+        grid::GridPosition,
+        input::{grid_dimensions, Input, ParseError},
+        intervals::IntervalSet,
+        par::par_sum,
+        render::{output_path, Color, GridRenderer},
+        rng::Rng,
+        schema::{Alphabet, GridSize, Schema},
+        FastHashMap,
+    },
 };
 
-fn part1() -> u32 {
-    let regions = load_regions();
-    regions.iter().map(Region::get_value).sum()
+fn part1(input: &str) -> u32 {
+    let regions = load_regions(input).unwrap();
+    render_regions(&regions, grid_dimensions(input).0);
+    sum_regions(&regions, Region::get_value)
+}
+
+/// Saves every region in its own color, if requested.
+fn render_regions(regions: &[Region], size: usize) {
+    let Some(path) = output_path("day12_regions.ppm") else {
+        return;
+    };
+    let mut region_of = vec![vec![0; size]; size];
+    for (i, region) in regions.iter().enumerate() {
+        for &(x, y) in region.points.keys() {
+            region_of[y as usize][x as usize] = i;
+        }
+    }
+    GridRenderer::new(size, size, |(row, column)| {
+        Color::from_index(region_of[row][column])
+    })
+    .save(path)
+    .expect("Could not save the regions");
+}
+
+fn part2(input: &str) -> u32 {
+    let regions = load_regions(input).unwrap();
+    sum_regions(&regions, Region::get_discount_value)
 }
 
-fn part2() -> u32 {
-    let _regions = load_regions();
-    _regions.iter().map(Region::get_discount_value).sum()
+fn sum_regions(regions: &[Region], value: fn(&Region) -> u32) -> u32 {
+    if cfg!(feature = "parallel") {
+        par_sum(regions, value)
+    } else {
+        regions.iter().map(value).sum()
+    }
 }
 
-struct Region<const SIZE: usize> {
+struct Region {
     area: u32,
-    points: HashMap<Coord, [bool; 4]>,
+    points: FastHashMap<Coord, [bool; 4]>,
 }
 
 type Coord = (isize, isize);
-impl<const SIZE: usize> Region<SIZE> {
-    fn load_region(coord: Coord, data: &Data<SIZE>, not_visited: &mut HashSet<Coord>) -> Self {
-        let mut queue = VecDeque::new();
-        queue.push_back(coord);
-        let mut found = HashMap::new();
-        while let Some(coord) = queue.pop_front() {
-            if found.contains_key(&coord) {
-                continue;
-            }
-            let neighbors = [
-                Self::neighbor_up(coord, data),
-                Self::neighbor_down(coord, data),
-                Self::neighbor_left(coord, data),
-                Self::neighbor_right(coord, data),
-            ];
-            found.insert(coord, neighbors);
-            not_visited.remove(&coord);
-            for point in data.get_sourounding(coord) {
-                queue.push_back(point);
-            }
-        }
-        let area = found.iter().count() as u32;
-        Self {
-            points: found,
-            area,
-        }
+impl Region {
+    fn new(cells: impl Iterator<Item = Coord>, data: &Data) -> Self {
+        let points: FastHashMap<Coord, [bool; 4]> = cells
+            .map(|coord| {
+                let neighbors = [
+                    Self::neighbor_up(coord, data),
+                    Self::neighbor_down(coord, data),
+                    Self::neighbor_left(coord, data),
+                    Self::neighbor_right(coord, data),
+                ];
+                (coord, neighbors)
+            })
+            .collect();
+        let area = points.len() as u32;
+        Self { points, area }
     }
     fn get_value(&self) -> u32 {
-        self.area * grahams_scan::<SIZE>(&self.points)
+        self.area * grahams_scan(&self.points)
     }
     #[allow(unused)]
     fn get_discount_value(&self) -> u32 {
@@ -67,178 +86,164 @@ impl<const SIZE: usize> Region<SIZE> {
             .points
             .iter()
             .filter(|(_, [top, ..])| !*top)
-            .map(|(&(x, y), _)| (y, ListElement { start: x, end: x }))
-            .merge_ranges();
+            .map(|(&(x, y), _)| (y, x))
+            .count_runs();
         let bottom_open = self
             .points
             .iter()
             .filter(|(_, [_, bottom, ..])| !*bottom)
-            .map(|(&(x, y), _)| (y, ListElement { start: x, end: x }))
-            .merge_ranges();
+            .map(|(&(x, y), _)| (y, x))
+            .count_runs();
         let left_open = self
             .points
             .iter()
             .filter(|(_, [_, _, left, _])| !*left)
-            .map(|(&(x, y), _)| (x, ListElement { start: y, end: y }))
-            .merge_ranges();
+            .map(|(&(x, y), _)| (x, y))
+            .count_runs();
         let right_open = self
             .points
             .iter()
             .filter(|(_, [_, _, _, right])| !*right)
-            .map(|(&(x, y), _)| (x, ListElement { start: y, end: y }))
-            .merge_ranges();
-        (top_open.len() + bottom_open.len() + left_open.len() + right_open.len()) as u32
+            .map(|(&(x, y), _)| (x, y))
+            .count_runs();
+        (top_open + bottom_open + left_open + right_open) as u32
     }
-    fn neighbor_left(coord: Coord, data: &Data<SIZE>) -> bool {
+    fn neighbor_left(coord: Coord, data: &Data) -> bool {
         data[coord] == data[(coord.0 - 1, coord.1)]
     }
-    fn neighbor_right(coord: Coord, data: &Data<SIZE>) -> bool {
+    fn neighbor_right(coord: Coord, data: &Data) -> bool {
         data[coord] == data[(coord.0 + 1, coord.1)]
     }
-    fn neighbor_up(coord: Coord, data: &Data<SIZE>) -> bool {
+    fn neighbor_up(coord: Coord, data: &Data) -> bool {
         data[coord] == data[(coord.0, coord.1 - 1)]
     }
-    fn neighbor_down(coord: Coord, data: &Data<SIZE>) -> bool {
+    fn neighbor_down(coord: Coord, data: &Data) -> bool {
         data[coord] == data[(coord.0, coord.1 + 1)]
     }
 }
-trait MergeRanges<T> {
-    fn merge_ranges(self) -> Vec<T>;
+trait CountRuns {
+    /// Counts the connected runs of cells, where every item is a `(line, cell)` pair and only
+    /// cells on the same line can be connected.
+    fn count_runs(self) -> usize;
 }
-impl<T> MergeRanges<(isize, ListElement)> for T
+impl<T> CountRuns for T
 where
-    T: Iterator<Item = (isize, ListElement)>,
+    T: Iterator<Item = (isize, isize)>,
 {
-    fn merge_ranges(self) -> Vec<(isize, ListElement)> {
-        let mut list = self.to_vec();
-        list.sort_by(|a, b| match a.0.cmp(&b.0) {
-            std::cmp::Ordering::Less => std::cmp::Ordering::Less,
-            std::cmp::Ordering::Greater => std::cmp::Ordering::Greater,
-            std::cmp::Ordering::Equal => a.1.start.cmp(&b.1.start),
-        });
-        let mut merged_ranges: Vec<(isize, ListElement)> = Vec::new();
-        for (same, range) in list {
-            if let Some((same_last, range_last)) = merged_ranges.last_mut() {
-                if *same_last == same && range_last.end + 1 == range.start {
-                    range_last.end = range.end;
-                    continue;
-                }
-            }
-            merged_ranges.push((same, range));
+    fn count_runs(self) -> usize {
+        let mut lines: FastHashMap<isize, IntervalSet<isize>> = FastHashMap::default();
+        for (line, cell) in self {
+            lines.entry(line).or_default().insert_point(cell);
         }
-        merged_ranges
-    }
-}
-struct ListElement {
-    start: isize,
-    end: isize,
-}
-impl Display for ListElement {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_fmt(format_args!("{}..{}", self.start, self.end))
-    }
-}
-impl Debug for ListElement {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        <Self as Display>::fmt(&self, f)
+        lines.values().map(IntervalSet::run_count).sum()
     }
 }
 
-fn grahams_scan<const SIZE: usize>(points: &HashMap<Coord, [bool; 4]>) -> u32 {
+fn grahams_scan(points: &FastHashMap<Coord, [bool; 4]>) -> u32 {
     points
         .values()
         .map(|&neighbor| neighbor.into_iter().filter(|&v| !v).count())
         .sum::<usize>() as u32
 }
 
-struct Data<const SIZE: usize> {
-    data: [[char; SIZE]; SIZE],
+struct Data {
+    data: Vec<Vec<char>>,
+}
+impl Data {
+    #[inline]
+    fn size(&self) -> usize {
+        self.data.len()
+    }
 }
 
-impl<const SIZE: usize> Index<Coord> for Data<SIZE> {
+impl Index<Coord> for Data {
     type Output = char;
 
     #[inline(always)]
     fn index(&self, index: Coord) -> &Self::Output {
-        if index.0 < 0 || index.1 < 0 || index.0 >= SIZE as isize || index.1 >= SIZE as isize {
+        let size = self.size() as isize;
+        if index.0 < 0 || index.1 < 0 || index.0 >= size || index.1 >= size {
             return &'\0';
         }
         &self.data[index.1 as usize][index.0 as usize]
     }
 }
 
-impl<const SIZE: usize> Data<SIZE> {
-    fn get_sourounding(&self, coord: Coord) -> Vec<Coord> {
-        let mut result = Vec::with_capacity(4);
-        let value = self[coord];
-        if coord.0 > 0 && self[(coord.0 - 1, coord.1)] == value {
-            result.push((coord.0 - 1, coord.1));
-        }
-        if coord.1 > 0 && self[(coord.0, coord.1 - 1)] == value {
-            result.push((coord.0, coord.1 - 1));
-        }
-        if coord.0 < SIZE as isize - 1 && self[(coord.0 + 1, coord.1)] == value {
-            result.push((coord.0 + 1, coord.1));
-        }
-        if coord.1 < SIZE as isize - 1 && self[(coord.0, coord.1 + 1)] == value {
-            result.push((coord.0, coord.1 + 1));
-        }
-        result
-    }
-}
-
-#[derive(Debug)]
-#[allow(unused)]
-enum DataError {
-    ParseIntError(ParseIntError),
-    TryFromSliceError(TryFromSliceError),
-    Other(&'static str),
-}
-impl From<ParseIntError> for DataError {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseIntError(value)
-    }
-}
-impl From<Vec<char>> for DataError {
-    fn from(_: Vec<char>) -> Self {
-        Self::Other("Could not convert Vector<char> to the array slice")
-    }
-}
-impl<const SIZE: usize> From<Vec<[char; SIZE]>> for DataError {
-    fn from(_: Vec<[char; SIZE]>) -> Self {
-        Self::Other("Could not convert Vector to the array slice")
-    }
-}
-impl<const SIZE: usize> FromStr for Data<SIZE> {
-    type Err = DataError;
+impl FromStr for Data {
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines: Vec<[char; SIZE]> = s
-            .trimmed_lines()
-            .map(|line| line.char_vec().try_into().map_err(Self::Err::from))
-            .collect_result()?;
-        let data: [[char; SIZE]; SIZE] = lines.try_into().map_err(Self::Err::from)?;
+        let data = s.grid()?;
+        let size = data.len();
+        if data.first().is_some_and(|row| row.len() != size) {
+            return Err(ParseError::Dimensions {
+                expected: (size, size),
+                found: (size, data[0].len()),
+            });
+        }
         Ok(Self { data })
     }
 }
 
-const STR: &str = include_str!("input.txt");
-const STR_LINES: usize = STR.len().isqrt();
-fn load_data() -> Data<STR_LINES> {
-    STR.trim().parse().unwrap()
+const INPUT: &str = include_str!("input.txt");
+/// The garden plots labelled by their plant.
+pub const SCHEMA: Schema = Schema::Grid {
+    size: GridSize::Square,
+    alphabet: Alphabet::Class("uppercase letters", char::is_ascii_uppercase),
+};
+fn load_data(input: &str) -> Result<Data, ParseError> {
+    input.trim().parse()
+}
+fn load_regions(input: &str) -> Result<Vec<Region>, ParseError> {
+    let data = load_data(input)?;
+    let size = data.size();
+    let to_coord = |(row, column): GridPosition| (column as isize, row as isize);
+    Ok(
+        grid_components(size, size, |a, b| data[to_coord(a)] == data[to_coord(b)])
+            .into_iter()
+            .map(|cells| Region::new(cells.into_iter().map(to_coord), &data))
+            .collect(),
+    )
 }
-fn load_regions() -> Vec<Region<STR_LINES>> {
-    let data = load_data();
-    let mut not_visited: HashSet<Coord> = (0..STR_LINES as isize)
-        .flat_map(|i| (0..STR_LINES as isize).map(move |j| (i, j)))
+
+/// Generates a `size` x `size` garden whose plots belong to the nearest of `size * size / 25`
+/// randomly planted seeds, which makes for irregular regions of the same plant.
+///
+/// Generates a `size` x `size` garden whose plots belong to the nearest of `size * size / 25`
+/// randomly planted seeds, which makes for irregular regions of the same plant.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let seeds: Vec<(GridPosition, char)> = (0..(size * size / 25).max(1))
+        .map(|_| {
+            let pos = (rng.range(0..size), rng.range(0..size));
+            (pos, (b'A' + rng.range(0..26) as u8) as char)
+        })
         .collect();
-    let mut regions = Vec::new();
-    while let Some(not_visited_point) = not_visited.iter().next() {
-        let region = Region::load_region(*not_visited_point, &data, &mut not_visited);
-        regions.push(region);
+    let mut result = String::new();
+    for row in 0..size {
+        for column in 0..size {
+            let (_, plant) = seeds
+                .iter()
+                .min_by_key(|((r, c), _)| r.abs_diff(row) + c.abs_diff(column))
+                .unwrap();
+            result.push(*plant);
+        }
+        result.push('\n');
     }
-    regions
+    result
+}
+
+pub fn fuzz(input: &str) {
+    let _ = load_regions(input);
 }
 
 build_run!(12, part1, part2);
 build_test!(part1: 1304764,part2: 811148);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 1..16,
+    reference: 4..16, Shrink::Grid { filler: '.' };
+    part1 => reference::part1,
+    part2 => reference::part2,
+);
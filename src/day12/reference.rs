@@ -0,0 +1,80 @@
+//! A naive solution to check the optimized one against.
+
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Returns the regions as lists of their cells, found with a flood fill.
+fn regions(grid: &[Vec<char>]) -> Vec<Vec<(isize, isize)>> {
+    let mut seen = vec![vec![false; grid[0].len()]; grid.len()];
+    let mut regions = Vec::new();
+    for row in 0..grid.len() {
+        for column in 0..grid[row].len() {
+            if seen[row][column] {
+                continue;
+            }
+            seen[row][column] = true;
+            let mut region = vec![(row as isize, column as isize)];
+            let mut i = 0;
+            while i < region.len() {
+                let (r, c) = region[i];
+                for (dr, dc) in DIRECTIONS {
+                    let next = (r + dr, c + dc);
+                    if plant(grid, next) == Some(grid[row][column])
+                        && !seen[next.0 as usize][next.1 as usize]
+                    {
+                        seen[next.0 as usize][next.1 as usize] = true;
+                        region.push(next);
+                    }
+                }
+                i += 1;
+            }
+            regions.push(region);
+        }
+    }
+    regions
+}
+fn plant(grid: &[Vec<char>], (row, column): (isize, isize)) -> Option<char> {
+    let row = grid.get(usize::try_from(row).ok()?)?;
+    row.get(usize::try_from(column).ok()?).copied()
+}
+
+fn price(input: &str, fences: fn(&[Vec<char>], (isize, isize)) -> usize) -> u32 {
+    let grid: Vec<Vec<char>> = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim().chars().collect())
+        .collect();
+    regions(&grid)
+        .iter()
+        .map(|region| {
+            let fences: usize = region.iter().map(|&cell| fences(&grid, cell)).sum();
+            (region.len() * fences) as u32
+        })
+        .sum()
+}
+
+pub(super) fn part1(input: &str) -> u32 {
+    price(input, |grid, (row, column)| {
+        let own = plant(grid, (row, column));
+        DIRECTIONS
+            .iter()
+            .filter(|(dr, dc)| plant(grid, (row + dr, column + dc)) != own)
+            .count()
+    })
+}
+
+/// Counts the sides by counting the corners of every cell, as a polygon has as many sides as
+/// corners.
+pub(super) fn part2(input: &str) -> u32 {
+    price(input, |grid, (row, column)| {
+        let own = plant(grid, (row, column));
+        let same = |dr: isize, dc: isize| plant(grid, (row + dr, column + dc)) == own;
+        (0..4)
+            .filter(|&i| {
+                let (a, b) = (DIRECTIONS[i], DIRECTIONS[(i + 1) % 4]);
+                let outer = !same(a.0, a.1) && !same(b.0, b.1);
+                let inner = same(a.0, a.1) && same(b.0, b.1) && !same(a.0 + b.0, a.1 + b.1);
+                outer || inner
+            })
+            .count()
+    })
+}
@@ -1,29 +1,82 @@
-use crate::{build_run, build_test, utilities::CollectIntoResult};
-fn part1() -> usize {
-    let data: Vec<Report> = load_data();
+use crate::{
+    build_checks, build_run, build_test,
+    utilities::{
+        input::{Input, ParseError},
+        rng::Rng,
+        schema::{LineShape, Schema},
+    },
+};
+fn part1(input: &str) -> usize {
+    let data: Vec<Report> = load_data(input).unwrap();
     data.into_iter()
         .filter(Report::is_safe_zero_tolerance)
         .count()
 }
 
-fn part2() -> usize {
-    let data: Vec<Report> = load_data();
+fn part2(input: &str) -> usize {
+    let data: Vec<Report> = load_data(input).unwrap();
     data.into_iter()
         .filter(Report::is_safe_one_tolerance)
         .count()
 }
-fn load_data() -> Vec<Report> {
-    let input_str: &str = include_str!("input.txt");
-    input_str
-        .trim()
-        .lines()
-        .map(str::parse::<Report>)
-        .collect_result()
-        .unwrap()
+const INPUT: &str = include_str!("input.txt");
+/// One report of levels per line.
+pub const SCHEMA: Schema = Schema::Lines {
+    shape: LineShape::Numbers {
+        separator: " ",
+        count: 1..=usize::MAX,
+    },
+    count: 1..=usize::MAX,
+};
+fn load_data(input: &str) -> Result<Vec<Report>, ParseError> {
+    Ok(input.parse_lines()?)
+}
+
+/// Generates `size` reports. A third of them is safe, a third is only safe after removing a
+/// duplicated level and the rest is noisy and most likely unsafe.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut result = String::new();
+    for _ in 0..size {
+        let direction = *rng.choose(&[-1, 1]);
+        let mut level = rng.range(30..70) as i32;
+        let mut levels = vec![level];
+        for _ in 1..rng.range(5..9) {
+            level += direction * rng.range(1..4) as i32;
+            levels.push(level);
+        }
+        match rng.range(0..3) {
+            0 => {}
+            1 => {
+                let i = rng.range(0..levels.len());
+                levels.insert(i, levels[i]);
+            }
+            _ => levels
+                .iter_mut()
+                .for_each(|level| *level += rng.range(0..7) as i32 - 3),
+        }
+        let levels: Vec<String> = levels.iter().map(i32::to_string).collect();
+        result += &levels.join(" ");
+        result.push('\n');
+    }
+    result
+}
+
+pub fn fuzz(input: &str) {
+    let _ = load_data(input);
+    let _ = input.parse::<Report>();
 }
 
 build_run!(2, part1, part2);
 build_test!(part1: 306, part2: 366);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 1..30,
+    reference: 1..30, Shrink::Lines;
+    part1 => reference::part1,
+    part2 => reference::part2,
+);
 
 mod report;
 use report::Report;
@@ -0,0 +1,40 @@
+//! A naive solution to check the optimized one against.
+
+fn reports(input: &str) -> Vec<Vec<i64>> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.split_whitespace()
+                .map(|n| n.parse().unwrap())
+                .collect()
+        })
+        .collect()
+}
+
+fn is_safe(levels: &[i64]) -> bool {
+    let increasing = levels.windows(2).all(|w| (1..=3).contains(&(w[1] - w[0])));
+    let decreasing = levels.windows(2).all(|w| (1..=3).contains(&(w[0] - w[1])));
+    levels.len() >= 2 && (increasing || decreasing)
+}
+
+pub(super) fn part1(input: &str) -> usize {
+    reports(input)
+        .iter()
+        .filter(|levels| is_safe(levels))
+        .count()
+}
+
+pub(super) fn part2(input: &str) -> usize {
+    reports(input)
+        .iter()
+        .filter(|levels| {
+            is_safe(levels)
+                || (0..levels.len()).any(|i| {
+                    let mut levels = levels.to_vec();
+                    levels.remove(i);
+                    is_safe(&levels)
+                })
+        })
+        .count()
+}
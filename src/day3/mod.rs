@@ -1,8 +1,15 @@
-use crate::{build_run, build_test, utilities::datatypes::num_wrapper::NumWrapper};
+use crate::{
+    build_checks, build_run, build_test,
+    utilities::{
+        datatypes::num_wrapper::NumWrapper,
+        rng::Rng,
+        schema::{Alphabet, LineShape, Schema},
+    },
+};
 
-fn part1() -> u32 {
-    let mut result = NumWrapper::new(0u32);
-    let instructions = load_instructions()
+fn part1(input: &str) -> u32 {
+    let mut result: NumWrapper<u32> = NumWrapper::new(0);
+    let instructions = load_instructions(input)
         .into_iter()
         .filter(Instruction::is_mul)
         .map(Instruction::unwrap_mul);
@@ -12,9 +19,9 @@ fn part1() -> u32 {
     *result
 }
 
-fn part2() -> u32 {
-    let mut result = NumWrapper::new(0);
-    let instructions = load_instructions();
+fn part2(input: &str) -> u32 {
+    let mut result: NumWrapper<u32> = NumWrapper::new(0);
+    let instructions = load_instructions(input);
     let mut dont = false;
     for instruction in instructions {
         match instruction {
@@ -35,27 +42,79 @@ use mul_instruction::*;
 mod instruction;
 use instruction::*;
 
-fn load_instructions() -> Vec<Instruction> {
-    let data = include_str!("input.txt");
+const INPUT: &str = include_str!("input.txt");
+/// Corrupted memory, which can contain any printable character.
+pub const SCHEMA: Schema = Schema::Lines {
+    shape: LineShape::Chars(Alphabet::PRINTABLE),
+    count: 1..=usize::MAX,
+};
+fn load_instructions(data: &str) -> Vec<Instruction> {
     let mut pointer = 0;
     let mut instructions = Vec::new();
     while (data.len() - pointer) > 8 {
-        let size = (data.len() - pointer).clamp(8, 12);
-        let sample = &data[pointer..pointer + size];
+        let mut end = pointer + (data.len() - pointer).clamp(8, 12);
+        while !data.is_char_boundary(end) {
+            end -= 1;
+        }
+        let sample = &data[pointer..end];
         if let Ok((instruction, used_length)) = MulInstruction::new(sample) {
             instructions.push(Instruction::Mul(instruction));
             pointer += used_length;
-        } else if &sample[0..7] == "don't()" {
+        } else if sample.starts_with("don't()") {
             instructions.push(Instruction::Dont);
             pointer += 7;
-        } else if &sample[0..4] == "do()" {
+        } else if sample.starts_with("do()") {
             instructions.push(Instruction::Do);
             pointer += 4;
         } else {
-            pointer += 1;
+            // skips the whole character, a single byte could end inside of it
+            pointer += sample.chars().next().map_or(1, char::len_utf8);
         }
     }
     instructions
 }
+/// Generates `size` tokens of corrupted memory: `mul(a,b)` instructions, `do()` and `don't()`
+/// switches, malformed look-alikes and random noise.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const MALFORMED: [&str; 8] = [
+        "mul(4*",
+        "mul(6,9!",
+        "?(12,34)",
+        "mul ( 2 , 4 )",
+        "mul[3,7]",
+        "mul(1234,5)",
+        "don't",
+        "mul(2,3",
+    ];
+    const NOISE: &[u8] = b"!@#$%^&*()[]{}<>,.;:'?/|~`+- _abdfhlmnortuwx0123456789";
+    let mut result = String::new();
+    for i in 1..=size {
+        match rng.range(0..10) {
+            0..=4 => result += &format!("mul({},{})", rng.range(1..1000), rng.range(1..1000)),
+            5 => result += rng.choose(&["do()", "don't()"]),
+            6 => result += rng.choose(&MALFORMED),
+            _ => (0..rng.range(1..8)).for_each(|_| result.push(*rng.choose(NOISE) as char)),
+        }
+        if i % 100 == 0 {
+            result.push('\n');
+        }
+    }
+    result
+}
+
+pub fn fuzz(input: &str) {
+    let _ = load_instructions(input);
+    let _ = MulInstruction::new(input);
+}
+
 build_run!(3, part1, part2);
 build_test!(part1: 173785482, part2: 83158140);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 1..40,
+    reference: 1..40, Shrink::Lines;
+    part1 => reference::part1,
+    part2 => reference::part2,
+);
@@ -19,13 +19,14 @@ impl MulInstruction {
     /// - `Err(&'static str)`: Returns an error message if the input string does not match the expected format or if parsing fails.
     ///
     /// # Errors
+    /// - If the string is shorter than 8 or longer than 12 bytes.
     /// - If the string does not start with "mul(".
     /// - If the values `num_a` or `num_b` are not valid integers with 1-3 digits.
     /// - If the string does not contain a comma ',' separating `num_a` and `num_b`.
     /// - If the string does not end with a closing parenthesis ')'.
     ///
     /// # Example
-    /// ```
+    /// ```ignore
     /// let input = "mul(123,456)";
     /// match MulInstruction::new(input) {
     ///     Ok((instruction, chars_used)) => {
@@ -37,8 +38,10 @@ impl MulInstruction {
     /// }
     /// ```
     pub fn new(data: &str) -> Result<(Self, usize), &'static str> {
-        assert!(data.len() >= 8 && data.len() <= 12);
-        if &data[0..4] != "mul(" {
+        if data.len() < 8 || data.len() > 12 {
+            return Err("The string does not have a length between 8 and 12");
+        }
+        if !data.starts_with("mul(") {
             return Err("The string does not start with a mul(");
         }
         let mut value_1 = 0;
@@ -90,9 +93,9 @@ impl MulInstruction {
         Ok((Self { value_1, value_2 }, v_2_offset + v_2_counter))
     }
 }
-impl<T> AddAssign<MulInstruction> for NumWrapper<T>
+impl<T, P> AddAssign<MulInstruction> for NumWrapper<T, P>
 where
-    NumWrapper<T>: AddAssign<T>,
+    NumWrapper<T, P>: AddAssign<T>,
     T: std::num::ZeroablePrimitive + From<u32>,
 {
     #[inline(always)]
@@ -102,13 +105,20 @@ where
 }
 #[test]
 fn test_mul_inst_new() -> Result<(), &'static str> {
-    let start = std::time::Instant::now();
     let (test, _) = MulInstruction::new("mul(1,2)")?;
     let expected = MulInstruction {
         value_1: 1,
         value_2: 2,
     };
     assert_eq!(test, expected);
-    println!("{:?}", start.elapsed());
     Ok(())
 }
+#[test]
+fn test_mul_inst_malformed() {
+    assert!(MulInstruction::new("mul(1").is_err());
+    assert!(MulInstruction::new("mu€(1,2)").is_err());
+    assert_eq!(
+        MulInstruction::new("mul(1,2)🎄").map(|(_, used)| used),
+        Ok(7)
+    );
+}
@@ -0,0 +1,43 @@
+//! A naive solution to check the optimized one against.
+
+/// Parses `mul(a,b)` at the start of `s`, where `a` and `b` have 1 to 3 digits.
+fn mul_at(s: &str) -> Option<u32> {
+    let rest = s.strip_prefix("mul(")?;
+    let (a, rest) = number_at(rest)?;
+    let rest = rest.strip_prefix(',')?;
+    let (b, rest) = number_at(rest)?;
+    rest.starts_with(')').then_some(a * b)
+}
+fn number_at(s: &str) -> Option<(u32, &str)> {
+    let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+    if !(1..=3).contains(&digits) {
+        return None;
+    }
+    Some((s[..digits].parse().ok()?, &s[digits..]))
+}
+
+fn sum_products(input: &str, with_switches: bool) -> u32 {
+    let mut enabled = true;
+    let mut result = 0;
+    for i in 0..input.len() {
+        let rest = &input[i..];
+        if rest.starts_with("do()") {
+            enabled = true;
+        } else if rest.starts_with("don't()") {
+            enabled = false;
+        } else if let Some(product) = mul_at(rest) {
+            if enabled || !with_switches {
+                result += product;
+            }
+        }
+    }
+    result
+}
+
+pub(super) fn part1(input: &str) -> u32 {
+    sum_products(input, false)
+}
+
+pub(super) fn part2(input: &str) -> u32 {
+    sum_products(input, true)
+}
@@ -1,51 +1,67 @@
 use std::ops::Range;
 
 use crate::{
-    build_run, build_test,
-    utilities::{IteratorHasNElementsOf, IteratorToVec as _, IteratorTrimmedLines, StringCharVec},
+    build_checks, build_run, build_test,
+    utilities::{
+        input::{Input, ParseError},
+        rng::Rng,
+        schema::{Alphabet, GridSize, Schema},
+        IteratorHasNElementsOf,
+    },
 };
 
-const SIZE: usize = 140;
-type Matrix = [[char; SIZE]; SIZE];
+const INPUT: &str = include_str!("input.txt");
+/// The word search of letters.
+pub const SCHEMA: Schema = Schema::Grid {
+    size: GridSize::Square,
+    alphabet: Alphabet::Chars("XMAS"),
+};
+type Matrix = [Vec<char>];
 const SEARCH_STR: [(char, usize); 4] = [('X', 0), ('S', 3), ('A', 2), ('M', 1)];
 const REVERSE_SEARCH_STR: [(char, usize); 4] = [('X', 3), ('S', 0), ('A', 1), ('M', 2)];
 
-fn part1() -> usize {
-    let data = load_data();
-    find_vertical(&data) + find_horizontal(&data) + find_diagonal(&data)
+fn part1(input: &str) -> usize {
+    let data = load_data(input).unwrap();
+    count_xmas(&data)
 }
 
-fn part2() -> usize {
-    let data = load_data();
+fn part2(input: &str) -> usize {
+    let data = load_data(input).unwrap();
     find_cross_mas(&data)
 }
 
-fn load_data() -> Matrix {
-    let data = include_str!("input.txt");
-    data.trimmed_lines()
-        .map(|line| line.char_vec().try_into().unwrap())
-        .to_vec()
-        .try_into()
-        .unwrap()
+fn load_data(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let grid = input.grid()?;
+    let size = grid.len();
+    if grid.first().is_some_and(|row| row.len() != size) {
+        return Err(ParseError::Dimensions {
+            expected: (size, size),
+            found: (size, grid[0].len()),
+        });
+    }
+    Ok(grid)
+}
+fn count_xmas(data: &Matrix) -> usize {
+    find_vertical(data) + find_horizontal(data) + find_diagonal(data)
 }
 fn find_vertical(data: &Matrix) -> usize {
     find_word(
         data,
-        |data: &[[char; 140]; 140], arr_index: usize, offset: usize, i: usize, c: char| -> bool {
+        |data: &Matrix, arr_index: usize, offset: usize, i: usize, c: char| -> bool {
             data[arr_index][offset + i] == c
         },
-        0..SIZE,
-        0..SIZE - 3,
+        0..data.len(),
+        0..data.len().saturating_sub(3),
     )
 }
 fn find_horizontal(data: &Matrix) -> usize {
     find_word(
         data,
-        |data: &[[char; 140]; 140], arr_index: usize, offset: usize, i: usize, c: char| -> bool {
+        |data: &Matrix, arr_index: usize, offset: usize, i: usize, c: char| -> bool {
             data[arr_index + i][offset] == c
         },
-        0..SIZE - 3,
-        0..SIZE,
+        0..data.len().saturating_sub(3),
+        0..data.len(),
     )
 }
 fn find_diagonal(data: &Matrix) -> usize {
@@ -54,21 +70,21 @@ fn find_diagonal(data: &Matrix) -> usize {
 fn find_diagonal_right(data: &Matrix) -> usize {
     find_word(
         data,
-        |data: &[[char; 140]; 140], arr_index: usize, offset: usize, i: usize, c: char| -> bool {
+        |data: &Matrix, arr_index: usize, offset: usize, i: usize, c: char| -> bool {
             data[arr_index + i][offset + i] == c
         },
-        0..SIZE - 3,
-        0..SIZE - 3,
+        0..data.len().saturating_sub(3),
+        0..data.len().saturating_sub(3),
     )
 }
 fn find_diagonal_left(data: &Matrix) -> usize {
     find_word(
         data,
-        |data: &[[char; 140]; 140], arr_index: usize, offset: usize, i: usize, c: char| -> bool {
+        |data: &Matrix, arr_index: usize, offset: usize, i: usize, c: char| -> bool {
             data[arr_index + i][offset - i] == c
         },
-        0..SIZE - 3,
-        3..SIZE,
+        0..data.len().saturating_sub(3),
+        3..data.len(),
     )
 }
 type IdentificationFunction = fn(data: &Matrix, usize, usize, usize, char) -> bool;
@@ -98,8 +114,9 @@ fn find_word(
 }
 
 fn find_cross_mas(data: &Matrix) -> usize {
-    (0..SIZE - 2)
-        .flat_map(|i| (0..SIZE - 2).map(move |j| (i + 1, j + 1)))
+    let inner = data.len().saturating_sub(2);
+    (0..inner)
+        .flat_map(|i| (0..inner).map(move |j| (i + 1, j + 1)))
         .filter(|&(x, y)| data[x][y] == 'A')
         .filter(|&(x, y)| {
             let bottom_left = data[x + 1][y - 1];
@@ -114,5 +131,30 @@ fn find_cross_mas(data: &Matrix) -> usize {
         })
         .count()
 }
+/// Generates a `size` x `size` grid of the letters of `XMAS`.
+///
+/// Generates a `size` x `size` grid of the letters of `XMAS`.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut result = String::new();
+    for _ in 0..size {
+        (0..size).for_each(|_| result.push(*rng.choose(&['X', 'M', 'A', 'S'])));
+        result.push('\n');
+    }
+    result
+}
+
+pub fn fuzz(input: &str) {
+    let _ = load_data(input);
+}
+
 build_run!(4, part1, part2);
 build_test!(part1: 2462, part2: 1877);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 1..16,
+    reference: 4..16, Shrink::Grid { filler: '.' };
+    part1 => reference::part1,
+    part2 => reference::part2,
+);
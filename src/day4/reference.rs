@@ -0,0 +1,53 @@
+//! A naive solution to check the optimized one against.
+
+fn grid(input: &str) -> Vec<Vec<char>> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.trim().chars().collect())
+        .collect()
+}
+fn letter(grid: &[Vec<char>], row: isize, column: isize) -> Option<char> {
+    let row = grid.get(usize::try_from(row).ok()?)?;
+    row.get(usize::try_from(column).ok()?).copied()
+}
+
+/// Counts `XMAS` in all eight directions starting at every cell.
+pub(super) fn part1(input: &str) -> usize {
+    let grid = grid(input);
+    let mut result = 0;
+    for row in 0..grid.len() as isize {
+        for column in 0..grid[0].len() as isize {
+            for dr in -1..=1 {
+                for dc in -1..=1 {
+                    result += "XMAS".chars().enumerate().all(|(i, c)| {
+                        let i = i as isize;
+                        letter(&grid, row + i * dr, column + i * dc) == Some(c)
+                    }) as usize;
+                }
+            }
+        }
+    }
+    result
+}
+
+/// Counts the `A`s whose diagonals both read `MAS` in either direction.
+pub(super) fn part2(input: &str) -> usize {
+    let grid = grid(input);
+    let mut result = 0;
+    for row in 0..grid.len() as isize {
+        for column in 0..grid[0].len() as isize {
+            let diagonal = |dr: isize, dc: isize| {
+                let ends = [
+                    letter(&grid, row - dr, column - dc),
+                    letter(&grid, row + dr, column + dc),
+                ];
+                ends == [Some('M'), Some('S')] || ends == [Some('S'), Some('M')]
+            };
+            if letter(&grid, row, column) == Some('A') && diagonal(1, 1) && diagonal(1, -1) {
+                result += 1;
+            }
+        }
+    }
+    result
+}
@@ -1,32 +1,27 @@
-use std::cmp::Ordering;
-
 use crate::{
-    build_run, build_test,
-    utilities::{CollectIntoResult, IteratorToVec, IteratorTrimmedLines, MapParse as _},
+    build_checks, build_run, build_test,
+    utilities::{
+        datatypes::NumberList,
+        input::{Input, ParseError},
+        rng::Rng,
+        schema::{LineShape, Schema},
+    },
 };
 
 mod page_ordering;
 use page_ordering::*;
 
 #[inline(always)]
-fn get_middle(data: Vec<u32>) -> u32 {
-    data[data.len() / 2]
+fn get_middle(data: NumberList<u32>) -> u32 {
+    data.middle().expect("An update contains at least one page")
 }
 
 fn is_in_right_order(page_ordering: &PageOrdering, update: &[u32]) -> bool {
-    update.is_sorted_by(|&a, &b| {
-        if a == b {
-            true
-        } else if page_ordering.should_be_before(a, b) {
-            true
-        } else {
-            false
-        }
-    })
+    page_ordering.respects(update)
 }
 
-fn part1() -> u32 {
-    let (page_ordering, updates) = load_data();
+fn part1(input: &str) -> u32 {
+    let (page_ordering, updates) = load_data(input).unwrap();
     updates
         .into_iter()
         .filter(|update| is_in_right_order(&page_ordering, update))
@@ -34,46 +29,97 @@ fn part1() -> u32 {
         .sum()
 }
 
-fn part2() -> u32 {
-    let (page_ordering, updates) = load_data();
+fn part2(input: &str) -> u32 {
+    let (page_ordering, updates) = load_data(input).unwrap();
     updates
         .into_iter()
         .filter(|update| !is_in_right_order(&page_ordering, update))
-        .map(|mut update| update_list(&page_ordering, &mut update))
+        .map(|update| update_list(&page_ordering, &update))
+        .map(NumberList::new)
         .map(get_middle)
         .sum()
 }
 
-fn update_list(page_ordering: &PageOrdering, update: &mut [u32]) -> Vec<u32> {
-    update.sort_by(|&a, &b| {
-        if a == b {
-            Ordering::Equal
-        } else if page_ordering.should_be_before(a, b) {
-            Ordering::Less
-        } else {
-            Ordering::Greater
+fn update_list(page_ordering: &PageOrdering, update: &[u32]) -> Vec<u32> {
+    page_ordering
+        .topological_sort(update)
+        .unwrap_or_else(|cycle| panic!("The page ordering rules contradict each other: {cycle}"))
+}
+
+const INPUT: &str = include_str!("input.txt");
+/// The page ordering rules, a blank line and the updates.
+pub const SCHEMA: Schema = Schema::Sections(&[
+    Schema::Lines {
+        shape: LineShape::Numbers {
+            separator: "|",
+            count: 2..=2,
+        },
+        count: 1..=usize::MAX,
+    },
+    Schema::Lines {
+        shape: LineShape::Numbers {
+            separator: ",",
+            count: 1..=usize::MAX,
+        },
+        count: 1..=usize::MAX,
+    },
+]);
+fn load_data(input: &str) -> Result<(PageOrdering, Vec<NumberList<u32>>), ParseError> {
+    let mut sections = input.sections();
+    let page_ordering_str = sections.next().ok_or(ParseError::Missing("the rules"))?;
+    let update_str = sections.next().ok_or(ParseError::Missing("the updates"))?;
+    let page_ordering_elements: Vec<PageOrderingElement> = page_ordering_str.parse_lines()?;
+    let page_ordering: PageOrdering = page_ordering_elements
+        .into_iter()
+        .map(PageOrderingElement::into)
+        .collect();
+    let updates: Vec<NumberList<u32>> =
+        update_str.parse_lines_with(|line| NumberList::parse_with_delimiter(line, ","))?;
+    Ok((page_ordering, updates))
+}
+
+/// Generates the rules for every pair of 49 pages and `size` updates of an odd length, half of
+/// them in the right order.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut pages: Vec<usize> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+    let mut rules = Vec::new();
+    for (i, before) in pages.iter().enumerate() {
+        for after in &pages[i + 1..] {
+            rules.push(format!("{before}|{after}\n"));
+        }
+    }
+    rng.shuffle(&mut rules);
+    let mut result = rules.concat();
+    for _ in 0..size {
+        result.push('\n');
+        let mut update: Vec<usize> = (0..pages.len()).collect();
+        rng.shuffle(&mut update);
+        update.truncate(rng.range(2..12) * 2 + 1);
+        if rng.chance(0.5) {
+            update.sort_unstable();
         }
-    });
-    update.to_vec()
+        let update: Vec<String> = update.iter().map(|&i| pages[i].to_string()).collect();
+        result += &update.join(",");
+    }
+    result.push('\n');
+    result
 }
 
-fn load_data() -> (PageOrdering, Vec<Vec<u32>>) {
-    let str = include_str!("input.txt");
-    let splited_text = str.trim().splitn(2, "\n\n").to_vec();
-    let [page_ordering_str, update_str] = [splited_text[0], splited_text[1]];
-    let page_ordering_elements: Vec<PageOrderingElement> = page_ordering_str
-        .trimmed_lines()
-        .parse()
-        .collect_result()
-        .unwrap();
-    let page_ordering = PageOrdering::new(page_ordering_elements);
-    let updates: Vec<Vec<u32>> = update_str
-        .trimmed_lines()
-        .map(|line| line.trim().split(',').parse().collect_result())
-        .collect_result()
-        .unwrap();
-    (page_ordering, updates)
+pub fn fuzz(input: &str) {
+    let _ = load_data(input);
+    let _ = input.parse::<PageOrderingElement>();
 }
 
 build_run!(5, part1, part2);
 build_test!(part1: 6498, part2: 5017);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 1..10,
+    reference: 1..10, Shrink::Lines;
+    part1 => reference::part1,
+    part2 => reference::part2,
+);
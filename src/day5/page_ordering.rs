@@ -1,31 +1,22 @@
-use std::{collections::HashSet, str::FromStr};
+use std::str::FromStr;
 
-use crate::utilities::{CollectIntoResult as _, MapParse as _};
+use crate::utilities::{input::ParseError, order::Relation, CollectIntoResult as _, MapParse as _};
+
+pub type PageOrdering = Relation<u32>;
 
-pub struct PageOrdering {
-    data: HashSet<(u32, u32)>,
-}
-impl PageOrdering {
-    pub fn new(data: Vec<PageOrderingElement>) -> Self {
-        Self { data: data.into_iter().map(PageOrderingElement::into).collect() }
-    }
-    pub fn should_be_before(&self, a: u32, b: u32) -> bool {
-        !self.data.contains(&(b, a))
-    }
-}
 pub struct PageOrderingElement {
     a: u32,
     b: u32,
 }
 impl FromStr for PageOrderingElement {
-    type Err = <u32 as FromStr>::Err;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let numbers: Vec<u32> = s.trim().splitn(2, '|').parse().collect_result()?;
-        Ok(Self {
-            a: numbers[0],
-            b: numbers[1],
-        })
+        match numbers[..] {
+            [a, b] => Ok(Self { a, b }),
+            _ => Err(ParseError::Missing("a page after the |")),
+        }
     }
 }
 impl Into<(u32, u32)> for PageOrderingElement {
@@ -40,4 +31,3 @@ impl PartialEq for PageOrderingElement {
         self.a == other.a && self.b == other.b
     }
 }
-
@@ -0,0 +1,67 @@
+//! A naive solution to check the optimized one against.
+
+struct Manual {
+    rules: Vec<(u32, u32)>,
+    updates: Vec<Vec<u32>>,
+}
+fn manual(input: &str) -> Manual {
+    let (rules, updates) = input.trim().split_once("\n\n").unwrap();
+    let rules = rules
+        .lines()
+        .map(|line| {
+            let (a, b) = line.trim().split_once('|').unwrap();
+            (a.parse().unwrap(), b.parse().unwrap())
+        })
+        .collect();
+    let updates = updates
+        .lines()
+        .map(|line| line.trim().split(',').map(|n| n.parse().unwrap()).collect())
+        .collect();
+    Manual { rules, updates }
+}
+impl Manual {
+    fn is_ordered(&self, update: &[u32]) -> bool {
+        (0..update.len())
+            .all(|i| (i + 1..update.len()).all(|j| !self.rules.contains(&(update[j], update[i]))))
+    }
+    /// Repeatedly takes the only page no other remaining page has to come before.
+    ///
+    /// # Panics
+    /// If the rules do not decide the order of the update.
+    fn order(&self, update: &[u32]) -> Vec<u32> {
+        let mut remaining = update.to_vec();
+        let mut ordered = Vec::new();
+        while !remaining.is_empty() {
+            let first: Vec<usize> = (0..remaining.len())
+                .filter(|&i| {
+                    remaining
+                        .iter()
+                        .all(|&other| !self.rules.contains(&(other, remaining[i])))
+                })
+                .collect();
+            assert_eq!(first.len(), 1, "The rules do not decide the order");
+            ordered.push(remaining.remove(first[0]));
+        }
+        ordered
+    }
+}
+
+pub(super) fn part1(input: &str) -> u32 {
+    let manual = manual(input);
+    manual
+        .updates
+        .iter()
+        .filter(|update| manual.is_ordered(update))
+        .map(|update| update[update.len() / 2])
+        .sum()
+}
+
+pub(super) fn part2(input: &str) -> u32 {
+    let manual = manual(input);
+    manual
+        .updates
+        .iter()
+        .filter(|update| !manual.is_ordered(update))
+        .map(|update| manual.order(update)[update.len() / 2])
+        .sum()
+}
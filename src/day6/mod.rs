@@ -1,46 +1,71 @@
-use std::{
-    fmt::{Display, Write},
-    marker::PhantomData,
-    ops::Range,
-    vec::IntoIter,
-};
-
-use crate::{build_run, build_test};
+use std::fmt::{Display, Write};
 
-const SIZE: usize = 130;
+use crate::{
+    build_checks, build_run, build_test,
+    utilities::{
+        animation::{self, Simulate},
+        bitset::BitGrid,
+        input::{Input, ParseError},
+        par::par_filter_count_with,
+        render::{Color, GridRenderer},
+        rng::Rng,
+        schema::{Alphabet, GridSize, Schema},
+    },
+};
 
-fn part1() -> usize {
-    let mut simulation = load_data();
-    simulation.run();
+fn part1(input: &str) -> usize {
+    let mut simulation = load_data(input).unwrap();
+    animation::run(&mut GuardWalk::new(&mut simulation), "day6_frames");
+    simulation.render().save_if_requested("day6_guard_path.ppm");
     simulation.visited_area.len()
 }
 
-fn part2() -> usize {
-    let simulation = load_data();
+fn part2(input: &str) -> usize {
+    let simulation = load_data(input).unwrap();
     let start_guard = simulation.guard;
     let mut first_simulation = simulation.clone();
     first_simulation.run();
-    let mut simulation = simulation.into_lightweight();
-    first_simulation
+    let candidates: Vec<Position> = first_simulation
         .visited_area
-        .into_iter()
+        .positions()
         .filter(|&point| point != start_guard.pos)
-        .filter_map(|point| {
-            simulation.reset(start_guard);
-            simulation.obstacles.push(point);
-            let result = if simulation.run() { Some(()) } else { None };
-            simulation.obstacles.pop();
-            result
-        })
-        .count()
+        .collect();
+    let creates_loop = |simulation: &mut LightWeightGuardSimulation, &point: &Position| {
+        simulation.reset(start_guard);
+        simulation.obstacles.push(point);
+        let result = simulation.run();
+        simulation.obstacles.pop();
+        result
+    };
+    if cfg!(feature = "parallel") {
+        par_filter_count_with(&candidates, || simulation.into_lightweight(), creates_loop)
+    } else {
+        let mut simulation = simulation.into_lightweight();
+        candidates
+            .iter()
+            .filter(|point| creates_loop(&mut simulation, point))
+            .count()
+    }
 }
-fn load_data() -> GuardSimulation {
-    let str = include_str!("input.txt");
-    let data = str
-        .trim()
-        .lines()
+const INPUT: &str = include_str!("input.txt");
+/// The map of the lab with its obstacles and the guard.
+pub const SCHEMA: Schema = Schema::Grid {
+    size: GridSize::Square,
+    alphabet: Alphabet::Chars(".#^"),
+};
+fn load_data(input: &str) -> Result<GuardSimulation, ParseError> {
+    let grid = input.grid()?;
+    let size = grid.len();
+    if grid.first().is_some_and(|row| row.len() != size) {
+        return Err(ParseError::Dimensions {
+            expected: (size, size),
+            found: (size, grid[0].len()),
+        });
+    }
+    let data = grid
+        .into_iter()
         .enumerate()
-        .flat_map(|(i, line)| line.trim().chars().enumerate().map(move |(j, c)| (i, j, c)))
+        .flat_map(|(i, row)| row.into_iter().enumerate().map(move |(j, c)| (i, j, c)))
         .filter(|&(_, _, c)| c != '.')
         .collect::<Vec<_>>();
     let obstacles = data
@@ -53,22 +78,55 @@ fn load_data() -> GuardSimulation {
         .filter(|&(_, _, c)| c == '^')
         .map(|(a, b, _)| (a, b))
         .next()
-        .unwrap();
-    GuardSimulation {
+        .ok_or(ParseError::Missing("the guard"))?;
+    Ok(GuardSimulation {
         guard: Guard {
             pos: guard_position,
             direction: Direction::Up,
         },
         obstacles,
         out_of_area: false,
-        visited_area: BitSet::<130, Position>::new(),
+        visited_area: BitGrid::new(size, size),
         in_loop: false,
+        size,
+    })
+}
+
+/// Generates a `size` x `size` map with about 5% obstacles and a guard facing up.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let guard = (rng.range(0..size), rng.range(0..size));
+    let mut result = String::new();
+    for row in 0..size {
+        for column in 0..size {
+            result.push(match (row, column) == guard {
+                true => '^',
+                false if rng.chance(0.05) => '#',
+                false => '.',
+            });
+        }
+        result.push('\n');
     }
+    result
+}
+
+pub fn fuzz(input: &str) {
+    let _ = load_data(input);
 }
 
 build_run!(6, part1, part2);
 build_test!(part1: 5269, part2: 1957);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 1..12,
+    reference: 1..12, Shrink::Grid { filler: '.' };
+    part1 => reference::part1,
+    part2 => reference::part2,
+);
 type Position = (usize, usize);
+/// Every `(position, direction)` state a guard has been in.
+type GuardMoves = BitGrid<4>;
 #[derive(PartialEq, Debug, Clone, Copy, Hash, Eq)]
 enum Direction {
     Up,
@@ -104,19 +162,27 @@ struct GuardSimulation {
     guard: Guard,
     obstacles: Vec<Position>,
     out_of_area: bool,
-    visited_area: BitSet<130, Position>,
+    visited_area: BitGrid,
     in_loop: bool,
+    size: usize,
+}
+/// A [`GuardSimulation`] together with the state needed to advance it one step at a time.
+struct GuardWalk<'a> {
+    simulation: &'a mut GuardSimulation,
+    moves: GuardMoves,
+    obstacles: BitGrid,
 }
 struct LightWeightGuardSimulation {
     guard: Guard,
     obstacles: Vec<Position>,
     out_of_area: bool,
     in_loop: bool,
+    moves: GuardMoves,
 }
 impl Display for GuardSimulation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for x in 0..SIZE {
-            for y in 0..SIZE {
+        for x in 0..self.size {
+            for y in 0..self.size {
                 let pos = (x, y);
                 if self.guard.pos == pos && !self.out_of_area {
                     f.write_char(match self.guard.direction {
@@ -127,7 +193,7 @@ impl Display for GuardSimulation {
                     })?;
                 } else if self.obstacles.contains(&pos) {
                     f.write_char('#')?;
-                } else if self.visited_area.contains(&pos) {
+                } else if self.visited_area.contains(pos) {
                     f.write_char('X')?;
                 } else {
                     f.write_char('.')?;
@@ -170,12 +236,23 @@ fn get_next_obstacle(guard: &Guard, obstacles: &Vec<Position>) -> Option<Positio
 impl GuardSimulation {
     #[inline]
     fn run(&mut self) -> bool {
-        let mut moves = BitSet::<130, Guard>::new();
+        let mut moves = GuardMoves::new(self.size, self.size);
         while !(self.out_of_area || self.in_loop) {
             self.step(&mut moves);
         }
         self.in_loop
     }
+    fn render(&self) -> GridRenderer<impl Fn(Position) -> Color + '_> {
+        GridRenderer::new(self.size, self.size, |pos| {
+            if self.obstacles.contains(&pos) {
+                Color::GRAY
+            } else if self.visited_area.contains(pos) {
+                Color::RED
+            } else {
+                Color::WHITE
+            }
+        })
+    }
     #[inline]
     fn into_lightweight(&self) -> LightWeightGuardSimulation {
         LightWeightGuardSimulation {
@@ -183,6 +260,7 @@ impl GuardSimulation {
             obstacles: self.obstacles.clone(),
             in_loop: false,
             out_of_area: false,
+            moves: GuardMoves::new(self.size, self.size),
         }
     }
     #[inline]
@@ -190,12 +268,11 @@ impl GuardSimulation {
         get_next_obstacle(&self.guard, &self.obstacles)
     }
     #[inline]
-    fn step(&mut self, moves: &mut BitSet<130, Guard>) {
-        if moves.contains(&self.guard) {
+    fn step(&mut self, moves: &mut GuardMoves) {
+        if !moves.insert_state(self.guard.pos, self.guard.direction.to_int()) {
             self.in_loop = true;
             return;
         }
-        moves.insert(&self.guard);
         if let Some(next_obstacle) = self.get_next_obstacle() {
             self.do_move(next_obstacle);
             self.guard.direction.rotate90();
@@ -215,7 +292,7 @@ impl GuardSimulation {
             }
             Direction::Right => {
                 let x = self.guard.pos.0;
-                for y in self.guard.pos.1..SIZE {
+                for y in self.guard.pos.1..self.size {
                     self.visited_area.insert((x, y));
                 }
             }
@@ -227,7 +304,7 @@ impl GuardSimulation {
             }
             Direction::Down => {
                 let y = self.guard.pos.1;
-                for x in self.guard.pos.0..SIZE {
+                for x in self.guard.pos.0..self.size {
                     self.visited_area.insert((x, y));
                 }
             }
@@ -267,124 +344,45 @@ impl GuardSimulation {
         }
     }
 }
-#[derive(Clone, Debug)]
-struct BitSet<const SIZE: usize, T> {
-    section_count: [u16; SIZE],
-    data: [[[bool; 4]; SIZE]; SIZE],
-    phantom: PhantomData<T>,
-}
-struct BitSetIntoIterator<const SIZE: usize, T> {
-    bitset: BitSet<SIZE, T>,
-    outer: usize,
-    inner: usize,
-    this_line: u16,
-}
-impl<const SIZE: usize> Iterator for BitSetIntoIterator<SIZE, Position> {
-    type Item = Position;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        while self.outer < SIZE {
-            if self.bitset.section_count[self.outer] < self.this_line {
-                self.outer += 1;
-                self.this_line = 0;
-                continue;
-            }
-            let x = self.outer;
-            while self.inner < SIZE {
-                let pos = (x, self.inner);
-                self.inner += 1;
-                if self.bitset.contains(&pos) {
-                    self.this_line += 1;
-                    return Some(pos);
-                }
-            }
-            self.inner = 0;
-            self.outer += 1;
-            self.this_line = 0;
-        }
-        None
-    }
-}
-impl<const SIZE: usize> IntoIterator for BitSet<SIZE, Position> {
-    type Item = Position;
-    type IntoIter = BitSetIntoIterator<SIZE, Position>;
-    fn into_iter(self) -> Self::IntoIter {
-        BitSetIntoIterator {
-            bitset: self,
-            outer: 0,
-            inner: 0,
-            this_line: 0,
+impl<'a> GuardWalk<'a> {
+    fn new(simulation: &'a mut GuardSimulation) -> Self {
+        let size = simulation.size;
+        let mut obstacles = BitGrid::new(size, size);
+        for &obstacle in &simulation.obstacles {
+            obstacles.insert(obstacle);
         }
-    }
-}
-impl<const SIZE: usize, T> BitSet<SIZE, T> {
-    fn len(&self) -> usize {
-        self.data
-            .map(|v| {
-                v.map(|v| v.into_iter().filter(|v| *v).count())
-                    .into_iter()
-                    .sum::<usize>()
-            })
-            .into_iter()
-            .sum()
-    }
-}
-impl<const SIZE: usize> BitSet<SIZE, Guard> {
-    fn new() -> Self {
         Self {
-            data: [[[false; 4]; SIZE]; SIZE],
-            section_count: [0; SIZE],
-            phantom: PhantomData::default(),
-        }
-    }
-    fn insert(&mut self, guard: &Guard) {
-        if !self.contains(guard) {
-            self.data[guard.pos.0][guard.pos.1][guard.direction.to_int()] = true;
-            self.section_count[guard.pos.0] += 1;
-        }
-    }
-    fn contains(&self, guard: &Guard) -> bool {
-        unsafe {
-            *self
-                .data
-                .get_unchecked(guard.pos.0)
-                .get_unchecked(guard.pos.1)
-                .get_unchecked(guard.direction.to_int())
+            simulation,
+            moves: GuardMoves::new(size, size),
+            obstacles,
         }
-        // self.data[guard.pos.0][guard.pos.1][guard.direction.to_int()]
     }
 }
-impl<const SIZE: usize> BitSet<SIZE, Position> {
-    fn new() -> Self {
-        Self {
-            data: [[[false; 4]; SIZE]; SIZE],
-            section_count: [0; SIZE],
-            phantom: PhantomData::default(),
-        }
+impl Simulate for GuardWalk<'_> {
+    fn step(&mut self) -> bool {
+        self.simulation.step(&mut self.moves);
+        !(self.simulation.out_of_area || self.simulation.in_loop)
     }
-    fn insert(&mut self, pos: Position) {
-        if !self.contains(&pos) {
-            self.data[pos.0][pos.1][0] = true;
-            self.section_count[pos.0] += 1;
-        }
+    fn dimensions(&self) -> (usize, usize) {
+        (self.simulation.size, self.simulation.size)
     }
-    fn contains(&self, pos: &Position) -> bool {
-        unsafe {
-            *self
-                .data
-                .get_unchecked(pos.0)
-                .get_unchecked(pos.1)
-                .get_unchecked(0)
+    fn color(&self, pos: Position) -> Color {
+        if pos == self.simulation.guard.pos && !self.simulation.out_of_area {
+            Color::GREEN
+        } else if self.obstacles.contains(pos) {
+            Color::GRAY
+        } else if self.simulation.visited_area.contains(pos) {
+            Color::RED
+        } else {
+            Color::WHITE
         }
-        // self.data[guard.pos.0][guard.pos.1][guard.direction.to_int()]
     }
 }
 impl LightWeightGuardSimulation {
     #[inline]
     fn run(&mut self) -> bool {
-        let mut moves = BitSet::<130, Guard>::new();
         while !(self.out_of_area || self.in_loop) {
-            self.step(&mut moves);
+            self.step();
         }
         self.in_loop
     }
@@ -393,12 +391,14 @@ impl LightWeightGuardSimulation {
         get_next_obstacle(&self.guard, &self.obstacles)
     }
     #[inline]
-    fn step(&mut self, moves: &mut BitSet<130, Guard>) {
-        if moves.contains(&self.guard) {
+    fn step(&mut self) {
+        if !self
+            .moves
+            .insert_state(self.guard.pos, self.guard.direction.to_int())
+        {
             self.in_loop = true;
             return;
         }
-        moves.insert(&self.guard);
         if let Some(next_obstacle) = self.get_next_obstacle() {
             self.do_move(next_obstacle);
             self.guard.direction.rotate90();
@@ -432,5 +432,6 @@ impl LightWeightGuardSimulation {
         self.guard = start_guard;
         self.in_loop = false;
         self.out_of_area = false;
+        self.moves.clear();
     }
 }
@@ -0,0 +1,79 @@
+//! A naive solution to check the optimized one against.
+
+use std::collections::HashSet;
+
+type Map = Vec<Vec<bool>>;
+type State = ((usize, usize), usize);
+
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+fn load(input: &str) -> (Map, (usize, usize)) {
+    let mut guard = None;
+    let map = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(row, line)| {
+            line.trim()
+                .chars()
+                .enumerate()
+                .map(|(column, c)| {
+                    if c == '^' {
+                        guard = Some((row, column));
+                    }
+                    c == '#'
+                })
+                .collect()
+        })
+        .collect();
+    (map, guard.unwrap())
+}
+
+/// Walks cell by cell until the guard leaves the map.
+///
+/// # Returns
+/// The visited cells, or `None` if the guard walks in a loop.
+fn walk(map: &Map, start: (usize, usize)) -> Option<HashSet<(usize, usize)>> {
+    let mut states: HashSet<State> = HashSet::new();
+    let (mut pos, mut direction) = (start, 0);
+    loop {
+        if !states.insert((pos, direction)) {
+            return None;
+        }
+        let (dr, dc) = DIRECTIONS[direction];
+        let row = pos.0 as isize + dr;
+        let column = pos.1 as isize + dc;
+        if row < 0 || column < 0 || row as usize >= map.len() || column as usize >= map[0].len() {
+            return Some(states.into_iter().map(|(pos, _)| pos).collect());
+        }
+        let next = (row as usize, column as usize);
+        if map[next.0][next.1] {
+            direction = (direction + 1) % 4;
+        } else {
+            pos = next;
+        }
+    }
+}
+
+pub(super) fn part1(input: &str) -> usize {
+    let (map, start) = load(input);
+    walk(&map, start).unwrap().len()
+}
+
+pub(super) fn part2(input: &str) -> usize {
+    let (mut map, start) = load(input);
+    let mut loops = 0;
+    for row in 0..map.len() {
+        for column in 0..map[row].len() {
+            if map[row][column] || (row, column) == start {
+                continue;
+            }
+            map[row][column] = true;
+            if walk(&map, start).is_none() {
+                loops += 1;
+            }
+            map[row][column] = false;
+        }
+    }
+    loops
+}
@@ -1,24 +1,34 @@
 use std::str::FromStr;
 
 use crate::{
-    build_run, build_test,
-    utilities::{CollectIntoResult, MapParse as _, ParseTrimmedLines as _},
+    build_checks, build_run, build_test,
+    utilities::{
+        digits::Digits,
+        input::{Input, ParseError},
+        par::par_sum,
+        rng::Rng,
+        schema::{LineShape, Schema},
+        CollectIntoResult, MapParse as _,
+    },
 };
 
-fn part1() -> u128 {
-    let data = load_equations();
-    data.into_iter()
-        .filter(Equation::is_possible_p1)
-        .map(|e| e.result)
-        .sum()
+fn part1(input: &str) -> u128 {
+    let data = load_equations(input).unwrap();
+    sum_possible(&data, Equation::is_possible_p1)
 }
 
-fn part2() -> u128 {
-    let data = load_equations();
-    data.into_iter()
-        .filter(Equation::is_possible_p2)
-        .map(|e| e.result)
-        .sum()
+fn part2(input: &str) -> u128 {
+    let data = load_equations(input).unwrap();
+    sum_possible(&data, Equation::is_possible_p2)
+}
+
+fn sum_possible(data: &[Equation], is_possible: fn(&Equation) -> bool) -> u128 {
+    let result = |e: &Equation| if is_possible(e) { e.result } else { 0 };
+    if cfg!(feature = "parallel") {
+        par_sum(data, result)
+    } else {
+        data.iter().map(result).sum()
+    }
 }
 
 struct Equation {
@@ -60,45 +70,87 @@ impl Equation {
         }
         self.test_is_possible_p2(n + self.values[i], i + 1)
             || self.test_is_possible_p2(n * self.values[i], i + 1)
-            || self.test_is_possible_p2(concat(n, self.values[i]), i + 1)
+            || self.test_is_possible_p2(n.concat(self.values[i], 10), i + 1)
     }
 }
-#[inline]
-fn concat(a: u128, b: u128) -> u128 {
-    a * 10u128.pow(count_digits(b)) + b
-}
-#[inline]
-fn count_digits(mut a: u128) -> u32 {
-    let mut result = 0;
-    while a != 0 {
-        result += 1;
-        a /= 10;
-    }
-    result
-}
 impl FromStr for Equation {
-    type Err = <u128 as FromStr>::Err;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut splited = s.trim().split(": ");
-        let result: u128 = splited.next().unwrap().trim().parse()?;
-        let values: Vec<u128> = splited
-            .next()
-            .unwrap()
+        let (result, values) = s
             .trim()
-            .split_whitespace()
-            .parse()
-            .collect_result()?;
+            .split_once(": ")
+            .ok_or(ParseError::Missing("the : after the test value"))?;
+        let result: u128 = result.trim().parse()?;
+        let values: Vec<u128> = values.trim().split_whitespace().parse().collect_result()?;
+        if values.is_empty() {
+            return Err(ParseError::Missing("the numbers of the equation"));
+        }
         Ok(Self::new(result, values))
     }
 }
 
-fn load_equations() -> Vec<Equation> {
-    include_str!("input.txt").parse_trimmed_lines().unwrap()
+const INPUT: &str = include_str!("input.txt");
+/// One calibration equation per line.
+pub const SCHEMA: Schema = Schema::Lines {
+    shape: LineShape::Split {
+        separator: ": ",
+        head: &LineShape::Numbers {
+            separator: " ",
+            count: 1..=1,
+        },
+        tail: &LineShape::Numbers {
+            separator: " ",
+            count: 1..=usize::MAX,
+        },
+    },
+    count: 1..=usize::MAX,
+};
+fn load_equations(input: &str) -> Result<Vec<Equation>, ParseError> {
+    Ok(input.parse_lines()?)
+}
+
+/// Generates `size` equations with 2 to 12 values. Two thirds of the results are reachable with
+/// random operators, the others are off by one and most likely not.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut result = String::new();
+    for _ in 0..size {
+        let values: Vec<u128> = (0..rng.range(2..13))
+            .map(|_| rng.range(1..1000) as u128)
+            .collect();
+        let mut value = values[0];
+        for &n in &values[1..] {
+            value = match rng.range(0..3) {
+                0 => value + n,
+                1 => value * n,
+                _ => value.concat(n, 10),
+            };
+        }
+        if rng.chance(1.0 / 3.0) {
+            value += 1;
+        }
+        let values: Vec<String> = values.iter().map(u128::to_string).collect();
+        result += &format!("{value}: {}\n", values.join(" "));
+    }
+    result
+}
+
+pub fn fuzz(input: &str) {
+    let _ = load_equations(input);
+    let _ = input.parse::<Equation>();
 }
 
 build_run!(7, part1, part2);
 build_test!(part1: 1430271835320, part2: 456565678667482);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 1..8,
+    reference: 1..8, Shrink::Lines;
+    part1 => reference::part1,
+    part2 => reference::part2,
+);
 #[test]
 fn test() {
     let test_eq = Equation::new(21037, vec![9, 7, 18, 3]);
@@ -106,5 +158,5 @@ fn test() {
 }
 #[test]
 fn test_cancat() {
-    assert_eq!(concat(10, 1), 101)
+    assert_eq!(10u128.concat(1, 10), 101)
 }
@@ -0,0 +1,49 @@
+//! A naive solution to check the optimized one against.
+
+fn equations(input: &str) -> Vec<(u128, Vec<u128>)> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (result, values) = line.split_once(':').unwrap();
+            let values: Vec<u128> = values
+                .split_whitespace()
+                .map(|v| v.parse().unwrap())
+                .collect();
+            assert!(values.iter().all(|&v| v > 0), "The values are positive");
+            (result.trim().parse().unwrap(), values)
+        })
+        .collect()
+}
+
+/// Tries every combination of the first `operators` operators of `+`, `*` and `||`.
+fn sum_possible(input: &str, operators: u32) -> u128 {
+    let mut result = 0;
+    for (target, values) in equations(input) {
+        let combinations = operators.pow(values.len() as u32 - 1);
+        let possible = (0..combinations).any(|mut combination| {
+            let mut value = values[0];
+            for &n in &values[1..] {
+                value = match combination % operators {
+                    0 => value + n,
+                    1 => value * n,
+                    _ => format!("{value}{n}").parse().unwrap(),
+                };
+                combination /= operators;
+            }
+            value == target
+        });
+        if possible {
+            result += target;
+        }
+    }
+    result
+}
+
+pub(super) fn part1(input: &str) -> u128 {
+    sum_possible(input, 2)
+}
+
+pub(super) fn part2(input: &str) -> u128 {
+    sum_possible(input, 3)
+}
@@ -78,7 +78,8 @@ impl Antenna {
     pub(super) fn coords(&self) -> (isize, isize) {
         (self.x, self.y)
     }
-    pub(super) fn is_possible(&self) -> bool {
-        self.x >= 0 && self.y >= 0 && self.x < super::STR_LINE_LEN && self.y < super::STR_LINE_LEN
+    /// Returns `true` if the antenna lies on a map with `size` rows and columns.
+    pub(super) fn is_possible(&self, size: isize) -> bool {
+        self.x >= 0 && self.y >= 0 && self.x < size && self.y < size
     }
 }
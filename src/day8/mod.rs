@@ -1,21 +1,28 @@
-use std::collections::HashSet;
+use crate::{
+    build_checks, build_run, build_test,
+    utilities::{
+        input::{grid_dimensions, Input, ParseError},
+        rational::gcd,
+        render::{output_path, Color, GridRenderer},
+        rng::Rng,
+        schema::{Alphabet, GridSize, Schema},
+        FastHashMap, FastHashSet, IteratorCombinatorics,
+    },
+};
 
-use crate::{build_run, build_test, utilities::IteratorTrimmedLines};
-
-fn part1() -> usize {
-    let data = load_data();
-    let mut set = HashSet::new();
+fn part1(input: &str) -> usize {
+    let size = grid_dimensions(input).0 as isize;
+    let data = load_data(input).unwrap();
+    let mut set = FastHashSet::default();
     for antenna_collection in data {
-        for (a1, a2) in antenna_collection
-            .iter()
-            .flat_map(|a1| antenna_collection.iter().map(move |a2| (a1, a2)))
-            .filter(|(a, b)| a != b)
-        {
+        for (a1, a2) in antenna_collection.iter().pairs() {
             let diff = a1 - a2;
             let p1 = a2 - diff;
-            if p1.is_possible() {}
+            if p1.is_possible(size) {
+                set.insert(p1.coords());
+            }
             let p2 = a1 + diff;
-            if p2.is_possible() {
+            if p2.is_possible(size) {
                 set.insert(p2.coords());
             }
         }
@@ -23,55 +30,82 @@ fn part1() -> usize {
     set.len()
 }
 
-fn part2() -> usize {
-    let data = load_data();
-    let mut set = HashSet::new();
-    for antenna_collection in data {
-        for (a1, a2) in antenna_collection
-            .iter()
-            .flat_map(|a1| antenna_collection.iter().map(move |a2| (a1, a2)))
-            .filter(|(a, b)| a != b)
-        {
-            if a1 == a2 {
-                continue;
-            }
-            set.insert(a1.coords());
-            set.insert(a2.coords());
-            let diff = a1 - a2;
-            let mut p1 = a2 - diff;
-            while p1.is_possible() {
+fn part2(input: &str) -> usize {
+    let size = grid_dimensions(input).0 as isize;
+    let data = load_data(input).unwrap();
+    let mut set = FastHashSet::default();
+    for antenna_collection in &data {
+        for (a1, a2) in antenna_collection.iter().pairs() {
+            // antennas whose offset shares a factor also have antinodes between the grid steps
+            let (dx, dy) = a1 - a2;
+            let divisor = gcd(dx, dy);
+            let step = (dx / divisor, dy / divisor);
+            let mut p1 = *a2;
+            while p1.is_possible(size) {
                 set.insert(p1.coords());
-                p1 = p1 - diff;
+                p1 = p1 - step;
             }
-            let mut p2 = a1 + diff;
-            while p2.is_possible() {
+            let mut p2 = a2 + step;
+            while p2.is_possible(size) {
                 set.insert(p2.coords());
-                p2 = p2 + diff;
+                p2 = p2 + step;
             }
         }
     }
+    render_antinodes(&data, &set, size as usize);
     set.len()
 }
 
+/// Saves the antennas colored by frequency on top of the white antinodes, if requested.
+fn render_antinodes(data: &[Vec<Antenna>], antinodes: &FastHashSet<(isize, isize)>, size: usize) {
+    let Some(path) = output_path("day8_antinodes.ppm") else {
+        return;
+    };
+    let frequencies: FastHashMap<(isize, isize), usize> = data
+        .iter()
+        .enumerate()
+        .flat_map(|(i, antennas)| antennas.iter().map(move |antenna| (antenna.coords(), i)))
+        .collect();
+    GridRenderer::new(size, size, |(row, column)| {
+        let coords = (row as isize, column as isize);
+        match frequencies.get(&coords) {
+            Some(&i) => Color::from_index(i),
+            None if antinodes.contains(&coords) => Color::WHITE,
+            None => Color::BLACK,
+        }
+    })
+    .save(path)
+    .expect("Could not save the antinodes");
+}
+
 mod antenna;
 use antenna::Antenna;
 
-const STR: &str = include_str!("input.txt");
-const STR_LINE_LEN: isize = STR.len().isqrt() as isize;
+const INPUT: &str = include_str!("input.txt");
+/// The map of the antennas, whose frequencies are letters and digits.
+pub const SCHEMA: Schema = Schema::Grid {
+    size: GridSize::Square,
+    alphabet: Alphabet::Class("'.', letters and digits", |c| {
+        *c == '.' || c.is_ascii_alphanumeric()
+    }),
+};
 
-fn load_data() -> Vec<Vec<Antenna>> {
+fn load_data(input: &str) -> Result<Vec<Vec<Antenna>>, ParseError> {
     let mut antennas: Vec<Vec<Antenna>> = Vec::new();
     let mut chars = [None; 127];
     let mut next = 0;
-    for (i, line) in STR.trimmed_lines().enumerate() {
-        for (j, char) in line.trim().chars().enumerate() {
+    for (i, row) in input.grid()?.into_iter().enumerate() {
+        for (j, char) in row.into_iter().enumerate() {
             if char == '.' {
                 continue;
             }
-            let index = if let Some(index) = chars[char as usize] {
+            let slot = chars
+                .get_mut(char as usize)
+                .ok_or(ParseError::UnexpectedChar(char))?;
+            let index = if let Some(index) = *slot {
                 index
             } else {
-                chars[char as usize] = Some(next);
+                *slot = Some(next);
                 next += 1;
                 next - 1
             };
@@ -84,8 +118,43 @@ fn load_data() -> Vec<Vec<Antenna>> {
             }
         }
     }
-    antennas
+    Ok(antennas)
+}
+
+/// Generates a `size` x `size` map with up to 62 frequencies of 3 to 5 antennas each.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let mut grid = vec![vec!['.'; size]; size];
+    for &frequency in &FREQUENCIES[..(size / 4).clamp(1, FREQUENCIES.len())] {
+        for _ in 0..rng.range(3..6) {
+            let cell = &mut grid[rng.range(0..size)][rng.range(0..size)];
+            if *cell == '.' {
+                *cell = frequency as char;
+            }
+        }
+    }
+    grid.into_iter()
+        .map(|row| row.into_iter().chain(['\n']).collect::<String>())
+        .collect()
+}
+
+pub fn fuzz(input: &str) {
+    let _ = load_data(input);
 }
 
 build_run!(8, part1, part2);
 build_test!(part1: 303, part2: 1045);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 4..16,
+    reference: 4..16, Shrink::Grid { filler: '.' };
+    part1 => reference::part1,
+    part2 => reference::part2,
+);
+#[test]
+fn test_antinodes_between_grid_steps() {
+    // the offset (2, 2) shares a factor, so (1, 1) and (3, 3) are in line with both antennas too
+    assert_eq!(part2("a....\n.....\n..a..\n.....\n....."), 5);
+}
@@ -0,0 +1,55 @@
+//! A naive solution to check the optimized one against.
+
+type Antenna = (char, isize, isize);
+
+fn load(input: &str) -> (Vec<Antenna>, isize) {
+    let lines: Vec<&str> = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect();
+    let antennas = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.trim()
+                .chars()
+                .enumerate()
+                .filter(|&(_, c)| c != '.')
+                .map(move |(column, c)| (c, row as isize, column as isize))
+        })
+        .collect();
+    (antennas, lines.len() as isize)
+}
+
+/// Counts the cells for which `is_antinode` holds with any pair of antennas of one frequency.
+fn count_antinodes(
+    input: &str,
+    is_antinode: fn((isize, isize), &Antenna, &Antenna) -> bool,
+) -> usize {
+    let (antennas, size) = load(input);
+    let cells = (0..size).flat_map(|row| (0..size).map(move |column| (row, column)));
+    cells
+        .filter(|&cell| {
+            antennas.iter().enumerate().any(|(i, a)| {
+                antennas[i + 1..]
+                    .iter()
+                    .any(|b| a.0 == b.0 && is_antinode(cell, a, b))
+            })
+        })
+        .count()
+}
+
+pub(super) fn part1(input: &str) -> usize {
+    count_antinodes(input, |(row, column), a, b| {
+        (row, column) == (2 * a.1 - b.1, 2 * a.2 - b.2)
+            || (row, column) == (2 * b.1 - a.1, 2 * b.2 - a.2)
+    })
+}
+
+pub(super) fn part2(input: &str) -> usize {
+    count_antinodes(input, |(row, column), a, b| {
+        let dr = (b.1 - a.1, row - a.1);
+        let dc = (b.2 - a.2, column - a.2);
+        dr.0 * dc.1 == dr.1 * dc.0
+    })
+}
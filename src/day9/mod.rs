@@ -1,104 +1,49 @@
+use std::ops::Range;
+
 use crate::{
-    build_run, build_test,
-    utilities::{IncrementAfter, IsEven, MoveElement},
+    build_checks, build_run, build_test, trace,
+    utilities::{
+        datatypes::num_wrapper::{Checked, NumWrapper},
+        input::ParseError,
+        intervals::IntervalSet,
+        rng::Rng,
+        schema::{Alphabet, LineShape, Schema},
+        CollectIntoResult,
+    },
 };
 
-fn part1() -> u128 {
-    let files = load_files();
+fn part1(input: &str) -> u128 {
+    let files = load_files(input).unwrap();
     let compressed_files = fill_gaps(files);
     checksum_files(&compressed_files)
 }
 
-fn part2() -> u128 {
-    let mut files = load_disk_space();
-    print_files(&files);
-    let mut i = files.len() - 1;
-    let mut c = 0;
-    while i != 0 {
-        let file = files[i];
-        if file.is_free_space() {
-            i -= 1;
-            continue;
-        }
-        if let Some(possible_pos) = first_possible_space(&files, &file, i) {
-            if false && files[i - 1].is_free_space() {
-                // println!("before:");
-                // print_files(&files);
-                (&mut files[i - 1]).increment_length(file.length());
-                files.move_element(i, possible_pos);
-            } else {
-                // println!("before:");
-                // print_files(&files);
-                c += 1;
-                files[i] = DiskSpace::Free {
-                    length: file.length(),
-                };
-                files.insert(possible_pos, file);
-                i += 1;
-            }
-            if let DiskSpace::Free { length } = files.get_mut(possible_pos + 1).unwrap() {
-                *length -= file.length();
-            }
-            //println!("after:");
-            //print_files(&files);
-        } else {
-            i -= 1;
-        }
-        while files.last().map(DiskSpace::is_free_space).unwrap_or(false) {
-            files.pop();
-            i -= 1;
-        }
-    }
-    println!("c: {c}");
-    checksum(&files)
-}
-
-fn first_possible_space(files: &Vec<DiskSpace>, file: &DiskSpace, current: usize) -> Option<usize> {
-    if file.is_free_space() {
-        return None;
-    }
-    let mut counter = 0;
-    for free_space in files {
-        counter += 1;
-        if counter >= current {
-            break;
-        }
-        if !free_space.is_free_space() {
-            continue;
-        }
-        if free_space.length() >= file.length() {
-            return Some(counter - 1);
+fn part2(input: &str) -> u128 {
+    let mut files = load_files(input).unwrap();
+    trace!(2 => "before: {}", format_files(&files));
+    let mut occupied: IntervalSet<u32> = files.iter().map(File::span).collect();
+    let mut moved = 0;
+    for file in files.iter_mut().rev() {
+        if let Some(gap) = occupied.first_gap(file.length, 0..file.start) {
+            moved += 1;
+            occupied.remove(file.span());
+            file.start = gap.start;
+            occupied.insert(file.span());
         }
     }
-    None
+    trace!("moved {moved} of {} files", files.len());
+    trace!(2 => "after: {}", format_files(&files));
+    checksum_files(&files)
 }
 
 fn checksum_files(files: &Vec<File>) -> u128 {
-    let mut result = 0;
+    let mut result = NumWrapper::<u128, Checked>::new(0);
     for file in files {
-        for counter in 0..file.length as u128 {
-            result += (file.start as u128 + counter) * (file.id as u128);
+        for position in file.span() {
+            result += NumWrapper::<u128, Checked>::new(position as u128) * file.id as u128;
         }
     }
-    result
-}
-
-fn checksum(files: &Vec<DiskSpace>) -> u128 {
-    let mut result = 0;
-    let mut from_start = 0;
-    for file in files {
-        match file {
-            DiskSpace::Free { length } => from_start += *length as u128,
-            DiskSpace::File { id, length } => {
-                for counter in 0..*length as u128 {
-                    let value = (from_start + counter) * (*id as u128);
-                    result += value;
-                }
-                from_start += file.length() as u128;
-            }
-        }
-    }
-    result
+    *result
 }
 
 fn fill_gaps(mut files: Vec<File>) -> Vec<File> {
@@ -136,35 +81,25 @@ impl File {
     fn new(id: usize, start: u32, length: u32) -> Self {
         Self { id, start, length }
     }
-}
-
-#[derive(Clone, Copy, Debug)]
-enum DiskSpace {
-    File { id: usize, length: u32 },
-    Free { length: u32 },
-}
-impl DiskSpace {
-    fn length(&self) -> u32 {
-        match self {
-            Self::File { length, .. } | Self::Free { length } => *length,
-        }
-    }
-    fn is_free_space(&self) -> bool {
-        match self {
-            Self::Free { .. } => true,
-            _ => false,
-        }
-    }
-    fn increment_length(&mut self, arg: u32) {
-        match self {
-            Self::File { length, .. } | Self::Free { length } => *length += arg,
-        }
+    #[inline]
+    fn span(&self) -> Range<u32> {
+        self.start..self.start + self.length
     }
 }
 
-const STR: &str = include_str!("input.txt");
-fn load_files() -> Vec<File> {
-    let mut numbers = STR.trim().chars().map(process_char).map(Option::unwrap);
+const INPUT: &str = include_str!("input.txt");
+/// The disk map on a single line.
+pub const SCHEMA: Schema = Schema::Lines {
+    shape: LineShape::Chars(Alphabet::DIGITS),
+    count: 1..=1,
+};
+fn load_files(input: &str) -> Result<Vec<File>, ParseError> {
+    let numbers: Vec<u32> = input
+        .trim()
+        .chars()
+        .map(|c| process_char(c).ok_or(ParseError::UnexpectedChar(c)))
+        .collect_result()?;
+    let mut numbers = numbers.into_iter();
     let mut i = 0;
     let mut files = Vec::new();
     while let Some(file_length) = numbers.next() {
@@ -174,45 +109,24 @@ fn load_files() -> Vec<File> {
             i += space_length;
         }
     }
-    files
+    Ok(files)
 }
 
-fn load_disk_space() -> Vec<DiskSpace> {
-    let mut file_counter = 0;
-    STR.trim()
-        .chars()
-        .map(process_char)
-        .map(Option::unwrap)
-        .enumerate()
-        .map(|(i, length)| {
-            if i.is_even() {
-                DiskSpace::File {
-                    id: file_counter.increment_after(),
-                    length,
-                }
-            } else {
-                DiskSpace::Free { length }
-            }
-        })
-        .collect()
-}
-fn print_files(files: &Vec<DiskSpace>) {
+fn format_files(files: &[File]) -> String {
+    let mut files = files.to_vec();
+    files.sort_by_key(|file| file.start);
     let mut buffer = String::new();
-    for space in files {
-        match space {
-            DiskSpace::File { id, length } => {
-                for _ in 0..*length {
-                    buffer.push_str(&format!("{}", id));
-                }
-            }
-            DiskSpace::Free { length } => {
-                for _ in 0..*length {
-                    buffer.push('.');
-                }
-            }
+    let mut position = 0;
+    for file in files {
+        for _ in position..file.start {
+            buffer.push('.');
+        }
+        for _ in 0..file.length {
+            buffer.push_str(&format!("{}", file.id));
         }
+        position = file.start + file.length;
     }
-    println!("{buffer}");
+    buffer
 }
 
 #[inline(always)]
@@ -220,5 +134,31 @@ fn process_char(c: char) -> Option<u32> {
     c.to_digit(10)
 }
 
+/// Generates a disk map of `size` files with 1 to 9 blocks and gaps of 0 to 9 blocks.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut result = String::new();
+    for i in 0..size {
+        if i > 0 {
+            result += &rng.range(0..10).to_string();
+        }
+        result += &rng.range(1..10).to_string();
+    }
+    result.push('\n');
+    result
+}
+
+pub fn fuzz(input: &str) {
+    let _ = load_files(input);
+}
+
 build_run!(9, part1, part2);
 build_test!(part1: 6398608069280);
+#[cfg(test)]
+mod reference;
+build_checks!(
+    schema: 20,
+    fuzz: 1..30,
+    reference: 1..30, Shrink::Lines;
+    part1 => reference::part1,
+    part2 => reference::part2,
+);
@@ -0,0 +1,54 @@
+//! A naive solution to check the optimized one against.
+
+/// Expands the disk map into one entry per block holding the id of its file.
+fn blocks(input: &str) -> Vec<Option<usize>> {
+    let mut blocks = Vec::new();
+    for (i, c) in input.trim().chars().enumerate() {
+        let length = c.to_digit(10).unwrap() as usize;
+        let id = (i % 2 == 0).then_some(i / 2);
+        blocks.extend(std::iter::repeat_n(id, length));
+    }
+    blocks
+}
+
+fn checksum(blocks: &[Option<usize>]) -> u128 {
+    blocks
+        .iter()
+        .enumerate()
+        .map(|(position, id)| (position * id.unwrap_or(0)) as u128)
+        .sum()
+}
+
+pub(super) fn part1(input: &str) -> u128 {
+    let mut blocks = blocks(input);
+    loop {
+        let gap = blocks.iter().position(Option::is_none);
+        let last = blocks.iter().rposition(Option::is_some);
+        match (gap, last) {
+            (Some(gap), Some(last)) if gap < last => blocks.swap(gap, last),
+            _ => break,
+        }
+    }
+    checksum(&blocks)
+}
+
+pub(super) fn part2(input: &str) -> u128 {
+    let mut blocks = blocks(input);
+    let files = input.trim().len().div_ceil(2);
+    for id in (0..files).rev() {
+        let Some(start) = blocks.iter().position(|&block| block == Some(id)) else {
+            continue;
+        };
+        let length = blocks[start..]
+            .iter()
+            .take_while(|&&block| block == Some(id))
+            .count();
+        let gap = (0..start).find(|&gap| blocks[gap..gap + length].iter().all(Option::is_none));
+        if let Some(gap) = gap {
+            for i in 0..length {
+                blocks.swap(gap + i, start + i);
+            }
+        }
+    }
+    checksum(&blocks)
+}
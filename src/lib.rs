@@ -0,0 +1,17 @@
+#![allow(internal_features)]
+#![feature(nonzero_internals)]
+#![feature(new_range_api)]
+#![feature(test)]
+use std::time::Duration;
+
+#[cfg(test)]
+extern crate test;
+
+mod macros;
+pub mod utilities;
+pub trait RunDay {
+    fn run() -> Duration;
+    /// Returns the answers of all parts for `input`.
+    fn solve(input: &str) -> Vec<String>;
+}
+build_mods!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
@@ -12,6 +12,45 @@ macro_rules! build_execute {
     };
 }
 #[macro_export]
+macro_rules! build_generate {
+    ($x:expr, $size:expr, $rng:expr, $($day:tt),*) => {
+        match $x {
+        $(
+            $day => {paste::paste! {
+                [< day $day >]::generate($size, $rng)
+            }},
+        )*
+        _ => panic!("The specified day is not available"),
+        }
+    };
+}
+#[macro_export]
+macro_rules! build_solve {
+    ($x:expr, $input:expr, $($day:tt),*) => {
+        match $x {
+        $(
+            $day => {paste::paste! {
+                [< day $day >]::[< Day $day >]::solve($input)
+            }},
+        )*
+        _ => panic!("The specified day is not available"),
+        }
+    };
+}
+#[macro_export]
+macro_rules! build_schema {
+    ($x:expr, $($day:tt),*) => {
+        match $x {
+        $(
+            $day => {paste::paste! {
+                &[< day $day >]::SCHEMA
+            }},
+        )*
+        _ => panic!("The specified day is not available"),
+        }
+    };
+}
+#[macro_export]
 macro_rules! build_run {
     ($day:tt, $function_1:ident$(, $function_2:ident)?) => {
         paste::paste! {
@@ -20,13 +59,13 @@ macro_rules! build_run {
             #[allow(unreachable_code)]
             fn run() -> std::time::Duration {
                 let start = std::time::Instant::now();
-                let result = $function_1();
+                let result = $function_1(INPUT);
                 let run_time = start.elapsed();
                 println!("The result of part 1 is: {result}");
                 println!("It took: {run_time:?}");
                 $(
                 let start_2 = std::time::Instant::now();
-                let result = $function_2();
+                let result = $function_2(INPUT);
                 let run_time_2 = start_2.elapsed();
                 println!("The result of part 2 is: {result}");
                 println!("It took: {run_time_2:?}");
@@ -34,6 +73,9 @@ macro_rules! build_run {
                 )?
                 return run_time;
             }
+            fn solve(input: &str) -> Vec<String> {
+                vec![$function_1(input).to_string() $(, $function_2(input).to_string())?]
+            }
         }
         }
     };
@@ -45,19 +87,154 @@ macro_rules! build_test {
         $(
             #[test]
             fn [< test _ $function:snake >]() {
-                assert_eq!($function(), $value);
+                assert_eq!($function(INPUT), $value);
             }
         )*
         }
     };
 }
+/// Generates the tests a day runs on the inputs of its `generate`: that they fit the `SCHEMA`,
+/// that `fuzz` does not panic on mutations of them, and that the solutions agree with the
+/// reference solutions.
+/// ```ignore
+/// build_checks!(
+///     schema: 20,
+///     fuzz: 1..30,
+///     reference: 1..30, Shrink::Lines;
+///     part1 => reference::part1,
+///     part2 => reference::part2,
+/// );
+/// ```
+#[macro_export]
+macro_rules! build_checks {
+    (
+        schema: $schema_size:expr,
+        fuzz: $fuzz_sizes:expr,
+        reference: $sizes:expr, $shrink:expr;
+        $($solution:expr => $reference:expr),+ $(,)?
+    ) => {
+        #[test]
+        fn test_against_reference() {
+            use $crate::utilities::differential::{assert_agree, Shrink};
+            $(
+                assert_agree(generate, $sizes, $shrink, $solution, $reference);
+            )+
+        }
+        #[test]
+        fn test_fuzz() {
+            $crate::utilities::fuzz::smoke(generate, $fuzz_sizes, fuzz);
+        }
+        #[test]
+        fn test_schema() {
+            let generated = generate($schema_size, &mut $crate::utilities::rng::Rng::new(0));
+            assert_eq!(SCHEMA.validate(&generated), vec![]);
+            assert_eq!(SCHEMA.validate(INPUT), vec![]);
+        }
+    };
+}
 #[macro_export]
 macro_rules! build_mods {
     ($($day:tt),+) => {
         paste::paste! {
         $(
-            mod [< day $day >];
+            pub mod [< day $day >];
         )*
         }
     };
 }
+/// Wraps a function with a cache keyed on its arguments.
+///
+/// The scope is either `global`, where results are kept for the whole run, or `per_call`, where
+/// results are dropped once the outermost call returns. Methods taking `&self` always need the
+/// `per_call` scope, because `self` is not part of the key.
+///
+/// Besides the function an accessor `<name>_cache()` is generated, which returns the thread local
+/// [`MemoCache`](crate::utilities::memo::MemoCache), e.g. to clear it or to read its statistics
+/// when the `debug` feature is enabled. Arguments and the result have to be `Clone`, the arguments
+/// also `Hash + Eq`, and the function can not be generic.
+/// ```ignore
+/// memoize!(global fn stone_count(value: u64, blinks: u32) -> u64 {
+///     // recursive calls to stone_count hit the cache
+/// });
+/// ```
+#[macro_export]
+macro_rules! memoize {
+    (@per_call global) => { false };
+    (@per_call per_call) => { true };
+    (@cached $scope:ident, $cache:expr, ($($arg:ident),*), $body:block) => {{
+        let key = ($($arg.clone(),)*);
+        let cache = $cache;
+        let _call = $crate::utilities::memo::CallGuard::enter(
+            cache,
+            $crate::memoize!(@per_call $scope),
+        );
+        match cache.with_borrow_mut(|cache| cache.get(&key)) {
+            Some(result) => result,
+            None => {
+                #[allow(clippy::redundant_closure_call)]
+                let result = (move || $body)();
+                cache.with_borrow_mut(|cache| cache.insert(key, result.clone()));
+                result
+            }
+        }
+    }};
+    (@accessor $vis:vis $name:ident, ($($ty:ty),*), $ret:ty) => {
+        paste::paste! {
+        #[allow(dead_code)]
+        $vis fn [< $name _cache >]() -> &'static std::thread::LocalKey<
+            std::cell::RefCell<$crate::utilities::memo::MemoCache<($($ty,)*), $ret>>,
+        > {
+            thread_local! {
+                static CACHE: std::cell::RefCell<
+                    $crate::utilities::memo::MemoCache<($($ty,)*), $ret>,
+                > = std::cell::RefCell::new($crate::utilities::memo::MemoCache::new());
+            }
+            &CACHE
+        }
+        }
+    };
+    (
+        per_call $(#[$meta:meta])* $vis:vis
+        fn $name:ident(&$self:ident $(, $arg:ident: $ty:ty)* $(,)?) -> $ret:ty $body:block
+    ) => {
+        $crate::memoize!(@accessor $vis $name, ($($ty),*), $ret);
+        $(#[$meta])*
+        $vis fn $name(&$self $(, $arg: $ty)*) -> $ret {
+            paste::paste! {
+                $crate::memoize!(@cached per_call, Self::[< $name _cache >](), ($($arg),*), $body)
+            }
+        }
+    };
+    (
+        $scope:ident $(#[$meta:meta])* $vis:vis
+        fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty $body:block
+    ) => {
+        $crate::memoize!(@accessor $vis $name, ($($ty),*), $ret);
+        $(#[$meta])*
+        $vis fn $name($($arg: $ty),*) -> $ret {
+            paste::paste! {
+                $crate::memoize!(@cached $scope, [< $name _cache >](), ($($arg),*), $body)
+            }
+        }
+    };
+}
+/// Writes a formatted line to the trace output if the runner was started with enough `-v` flags.
+///
+/// Without an explicit level the line is written from verbosity `1` on. The arguments are only
+/// evaluated if the line is written, so expensive renderings of intermediate state cost nothing
+/// in normal runs.
+/// ```ignore
+/// trace!("moved {moved} files");
+/// trace!(2 => "{}", format_files(&files));
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($level:literal => $($arg:tt)+) => {
+        if $crate::utilities::trace::enabled($level) {
+            $crate::utilities::trace::write(format_args!($($arg)+));
+        }
+    };
+    ($($arg:tt)+) => {
+        $crate::trace!(1 => $($arg)+)
+    };
+}
@@ -1,25 +1,207 @@
-#![feature(iter_map_windows)]
-#![allow(internal_features)]
-#![feature(nonzero_internals)]
-#![feature(isqrt)]
-#![feature(option_array_transpose)]
-#![feature(new_range_api)]
-use std::time::Duration;
+use std::path::{Path, PathBuf};
+
+use aoc24::*;
 
-mod macros;
-mod utilities;
-trait RunDay {
-    fn run() -> Duration;
-}
-build_mods!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
 fn main() {
-    if std::env::args().count() <= 1 {
+    let mut day = None;
+    let (mut inputs_dir, mut answers, mut record) = (None, None, false);
+    let mut args = std::env::args().skip(1).peekable();
+    if args.next_if(|arg| arg == "gen").is_some() {
+        return generate(args);
+    }
+    if args.next_if(|arg| arg == "check").is_some() {
+        return check(args);
+    }
+    // `run` is optional, `aoc24 run 5` is the same as `aoc24 5`
+    args.next_if(|arg| arg == "run");
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--animate" => utilities::animation::enable(Default::default()),
+            "--fps" => {
+                let fps = args.next().and_then(|fps| fps.parse().ok());
+                let fps = fps.expect("--fps needs a positive number of frames per second");
+                utilities::animation::enable(utilities::animation::AnimationSettings { fps });
+            }
+            "--inputs-dir" => {
+                inputs_dir = Some(PathBuf::from(
+                    args.next().expect("--inputs-dir needs a directory"),
+                ));
+            }
+            "--answers" => {
+                answers = Some(PathBuf::from(args.next().expect("--answers needs a file")))
+            }
+            "--record" => record = true,
+            "--verbose" => utilities::trace::set_level(utilities::trace::level() + 1),
+            "--render-dir" => {
+                let dir = args.next().expect("--render-dir needs a directory");
+                utilities::render::set_output_dir(&dir)
+                    .unwrap_or_else(|err| panic!("Could not create the directory {dir}: {err}"));
+            }
+            "--trace-file" => {
+                let path = args.next().expect("--trace-file needs a path");
+                utilities::trace::set_trace_file(&path)
+                    .unwrap_or_else(|err| panic!("Could not create the trace file {path}: {err}"));
+            }
+            flags if flags.len() > 1 && flags.strip_prefix('-').is_some_and(is_verbose_flags) => {
+                let level = utilities::trace::level() + flags.len() as u8 - 1;
+                utilities::trace::set_level(level);
+            }
+            _ => day = Some(arg.parse::<u8>().unwrap()),
+        }
+    }
+    let Some(x) = day else {
         return;
+    };
+    match inputs_dir {
+        Some(dir) => {
+            let answers = answers.unwrap_or_else(|| dir.join("answers.txt"));
+            run_inputs(x, &dir, &answers, record);
+        }
+        None => {
+            build_execute!(x, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+        }
+    }
+    utilities::trace::flush().expect("Could not write the trace file");
+}
+/// Solves every file in `dir` for `aoc24 run <day> --inputs-dir <dir> [--answers <file>]`.
+///
+/// The answers are looked up in the answer store by the hash of the input, and every input is
+/// reported as passed, failed or unknown. With `--record` the answers of unknown inputs are added
+/// to the store. The process exits with `1` if any input failed.
+fn run_inputs(day: u8, dir: &Path, answers_path: &Path, record: bool) {
+    use utilities::answers::{input_hash, AnswerStore, Verdict};
+    let mut store = AnswerStore::load(answers_path).unwrap_or_else(|err| {
+        panic!(
+            "Could not read the answers {}: {err}",
+            answers_path.display()
+        )
+    });
+    let store_file = answers_path.canonicalize().ok();
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("Could not read the directory {}: {err}", dir.display()))
+        .map(|entry| entry.expect("Could not read the directory").path())
+        .filter(|path| path.is_file() && path.canonicalize().ok() != store_file)
+        .collect();
+    files.sort();
+    let (mut passed, mut failed, mut unknown) = (0, 0, 0);
+    // the panics of broken inputs are reported below, instead of with a backtrace
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    for path in files {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let input = match std::fs::read_to_string(&path) {
+            Ok(input) => input,
+            Err(err) => {
+                println!("{name}: could not read the input: {err}");
+                failed += 1;
+                continue;
+            }
+        };
+        let start = std::time::Instant::now();
+        let solved = std::panic::catch_unwind(|| {
+            build_solve!(day, &input, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12)
+        });
+        let run_time = start.elapsed();
+        let answers = match solved {
+            Ok(answers) => answers,
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<String>()
+                    .map(String::as_str)
+                    .or_else(|| payload.downcast_ref::<&str>().copied())
+                    .unwrap_or("unknown panic");
+                println!("{name}: panicked: {message}");
+                failed += 1;
+                continue;
+            }
+        };
+        let hash = input_hash(&input);
+        let verdict = store.verdict(day, hash, &answers);
+        println!("{name}: {} [{verdict}] ({run_time:?})", answers.join(", "));
+        match verdict {
+            Verdict::Pass => passed += 1,
+            Verdict::Fail { .. } => failed += 1,
+            Verdict::Unknown => {
+                unknown += 1;
+                if record {
+                    store.insert(day, hash, answers);
+                }
+            }
+        }
+    }
+    std::panic::set_hook(hook);
+    println!("{passed} passed, {failed} failed, {unknown} unknown");
+    if record && unknown > 0 {
+        store.save(answers_path).unwrap_or_else(|err| {
+            panic!(
+                "Could not write the answers {}: {err}",
+                answers_path.display()
+            )
+        });
+        println!("Recorded {unknown} answers in {}", answers_path.display());
+    }
+    if failed > 0 {
+        std::process::exit(1);
     }
-    let x = std::env::args()
-        .nth(1)
-        .map(|str| str.parse::<u8>().ok())
-        .flatten()
-        .unwrap();
-    build_execute!(x, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+}
+/// Prints a synthetic input for `aoc24 gen <day> [--size N] [--seed S]`.
+///
+/// What the size means depends on the day, e.g. the number of lines or the side of a grid. The
+/// same seed always yields the same input.
+fn generate(mut args: impl Iterator<Item = String>) {
+    let (mut day, mut size, mut seed) = (None, 100, 0);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                let value = args.next().and_then(|size| size.parse().ok());
+                let value = value.filter(|&size| size > 0);
+                size = value.expect("--size needs a positive number");
+            }
+            "--seed" => {
+                let value = args.next().and_then(|seed| seed.parse().ok());
+                seed = value.expect("--seed needs a number");
+            }
+            _ => day = Some(arg.parse::<u8>().unwrap()),
+        }
+    }
+    let day = day.expect("gen needs a day");
+    let mut rng = utilities::rng::Rng::new(seed);
+    print!(
+        "{}",
+        build_generate!(day, size, &mut rng, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12)
+    );
+}
+/// Validates a file for `aoc24 check <day> <file>` against the input schema of the day.
+///
+/// Every mismatch is printed, and the process exits with `1` if there is any.
+fn check(mut args: impl Iterator<Item = String>) {
+    const SHOWN: usize = 20;
+    let day: u8 = args
+        .next()
+        .and_then(|day| day.parse().ok())
+        .expect("check needs a day");
+    let path = args.next().expect("check needs an input file");
+    let bytes = std::fs::read(&path).unwrap_or_else(|err| panic!("Could not read {path}: {err}"));
+    let Ok(input) = String::from_utf8(bytes) else {
+        println!("{path}: the input is not UTF-8 text");
+        std::process::exit(1);
+    };
+    let schema: &utilities::schema::Schema =
+        build_schema!(day, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12);
+    let mismatches = schema.validate(&input);
+    if mismatches.is_empty() {
+        println!("{path} is a valid input for day {day}");
+        return;
+    }
+    for mismatch in mismatches.iter().take(SHOWN) {
+        println!("{path}: {mismatch}");
+    }
+    if mismatches.len() > SHOWN {
+        println!("... and {} more", mismatches.len() - SHOWN);
+    }
+    std::process::exit(1);
+}
+/// Returns `true` for the `v`s of stacked verbosity flags like `-vv`.
+fn is_verbose_flags(flags: &str) -> bool {
+    flags.chars().all(|c| c == 'v')
 }
@@ -1,10 +1,37 @@
-use std::{
-    num::ZeroablePrimitive,
-    ops::{Add, BitAnd, DivAssign},
-    str::FromStr,
-};
-
+use std::{ops::BitAnd, str::FromStr};
+
+pub mod animation;
+pub mod answers;
+mod array_vec;
+pub use array_vec::ArrayVec;
+pub mod bitset;
+mod combinatorics;
+pub use combinatorics::*;
+mod counter;
+pub use counter::Counter;
+pub mod cycle;
 pub mod datatypes;
+#[cfg(test)]
+pub mod differential;
+pub mod digits;
+#[cfg(test)]
+pub mod fuzz;
+pub mod grid;
+mod hash;
+pub use hash::{FastHashMap, FastHashSet};
+pub mod dsu;
+pub mod heap;
+pub mod input;
+pub mod intervals;
+pub mod linear_system;
+pub mod memo;
+pub mod order;
+pub mod par;
+pub mod rational;
+pub mod render;
+pub mod rng;
+pub mod schema;
+pub mod trace;
 
 pub trait CollectIntoResult<T, E, W>: Iterator
 where
@@ -73,20 +100,6 @@ where
 
 impl<I> IteratorWithout for I where I: Iterator + Clone {}
 
-pub trait IteratorTrimmedLines {
-    fn trimmed_lines(&self) -> std::str::Lines;
-}
-impl IteratorTrimmedLines for &str {
-    fn trimmed_lines(&self) -> std::str::Lines {
-        self.trim().lines().into_iter()
-    }
-}
-impl IteratorTrimmedLines for String {
-    #[inline]
-    fn trimmed_lines(&self) -> std::str::Lines {
-        self.trim().lines().into_iter()
-    }
-}
 pub trait StringCharVec {
     fn char_vec(&self) -> Vec<char>;
 }
@@ -151,45 +164,6 @@ where
     }
 }
 
-pub trait ParseTrimmedLines<T>
-where
-    Self: IteratorTrimmedLines,
-    T: FromStr,
-{
-    fn parse_trimmed_lines(self) -> Result<Vec<T>, <T as FromStr>::Err>;
-}
-
-impl<T, T0> ParseTrimmedLines<T> for T0
-where
-    T0: IteratorTrimmedLines,
-    T: FromStr,
-{
-    #[inline]
-    fn parse_trimmed_lines(self) -> Result<Vec<T>, <T as FromStr>::Err> {
-        self.trimmed_lines().parse().collect_result()
-    }
-}
-
-pub trait MoveElement {
-    fn move_element(&mut self, from: usize, to: usize);
-}
-
-impl<T> MoveElement for Vec<T> {
-    fn move_element(&mut self, from: usize, to: usize) {
-        if from < self.len() && to < self.len() && from != to {
-            if from < to {
-                for i in from..to {
-                    self.swap(i, i + 1);
-                }
-            } else {
-                for i in (to + 1..from + 1).rev() {
-                    self.swap(i, i - 1);
-                }
-            }
-        }
-    }
-}
-
 pub trait IsEven {
     fn is_even(&self) -> bool;
 }
@@ -204,43 +178,6 @@ where
     }
 }
 
-pub trait IncrementAfter
-where
-    Self: Add + Sized,
-{
-    fn increment_after(&mut self) -> Self;
-}
-
-impl<T> IncrementAfter for T
-where
-    T: Sized + Add<Output = T> + From<u8> + Copy,
-{
-    fn increment_after(&mut self) -> Self {
-        let temp = *self;
-        *self = *self + 1u8.into();
-        temp
-    }
-}
-
-pub trait DigitCount {
-    fn digit_count(&self) -> usize;
-}
-
-impl<T> DigitCount for T
-where
-    T: ZeroablePrimitive + Copy + From<u8> + PartialEq + DivAssign,
-{
-    fn digit_count(&self) -> usize {
-        let mut count = 0;
-        let mut data = *self;
-        while data != 0u8.into() {
-            count += 1;
-            data /= 10u8.into();
-        }
-        count
-    }
-}
-
 #[test]
 fn test_collect_result() {
     let test_data = vec!["1", "2", "3"];
@@ -0,0 +1,227 @@
+//! Frame by frame recording of grid simulations.
+//!
+//! The final state of a simulation often hides where it went wrong, so days implementing
+//! [`Simulate`] can be watched step by step: the recorded frames are played back in the terminal
+//! and, if the runner was given a render directory, saved as a numbered PPM sequence.
+
+use std::{
+    io::Write,
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use super::{
+    grid::GridPosition,
+    render::{output_path, Color, GridRenderer},
+};
+
+static SETTINGS: Mutex<Option<AnimationSettings>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnimationSettings {
+    /// The playback speed in frames per second.
+    pub fps: u32,
+}
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self { fps: 20 }
+    }
+}
+
+/// Enables the recording of every following [`run`].
+pub fn enable(settings: AnimationSettings) {
+    assert!(
+        settings.fps > 0,
+        "The playback needs at least one frame per second"
+    );
+    *SETTINGS.lock().unwrap() = Some(settings);
+}
+/// Returns the settings if the runner was asked to animate.
+pub fn settings() -> Option<AnimationSettings> {
+    *SETTINGS.lock().unwrap()
+}
+
+/// A grid simulation that advances in discrete steps.
+pub trait Simulate {
+    /// Advances the simulation by a single step.
+    ///
+    /// # Returns
+    /// `false` once the simulation is finished.
+    fn step(&mut self) -> bool;
+    /// Returns the amount of rows and columns of the grid.
+    fn dimensions(&self) -> (usize, usize);
+    /// Returns the color of the cell at `pos` in the current state.
+    fn color(&self, pos: GridPosition) -> Color;
+}
+
+/// Runs `simulation` until it is finished.
+///
+/// If animations are enabled, every step is recorded and played back afterwards. The frames are
+/// additionally saved into the directory `name` of the render output, if one was requested.
+pub fn run(simulation: &mut impl Simulate, name: &str) {
+    let Some(settings) = settings() else {
+        while simulation.step() {}
+        return;
+    };
+    let mut recorder = FrameRecorder::new();
+    recorder.record(simulation);
+    while simulation.step() {
+        recorder.record(simulation);
+    }
+    recorder.record(simulation);
+    if let Some(dir) = output_path(name) {
+        recorder
+            .save_frames(&dir)
+            .unwrap_or_else(|err| panic!("Could not save the frames to {}: {err}", dir.display()));
+    }
+    recorder
+        .play(std::io::stderr().lock(), settings.fps)
+        .expect("Could not play the animation");
+}
+
+/// A snapshot of the colors of every cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    rows: usize,
+    columns: usize,
+    cells: Vec<Color>,
+}
+impl Frame {
+    pub fn capture(simulation: &impl Simulate) -> Self {
+        let (rows, columns) = simulation.dimensions();
+        let cells = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (row, column)))
+            .map(|pos| simulation.color(pos))
+            .collect();
+        Self {
+            rows,
+            columns,
+            cells,
+        }
+    }
+    #[inline]
+    pub fn get(&self, (row, column): GridPosition) -> Color {
+        self.cells[row * self.columns + column]
+    }
+    pub fn renderer(&self) -> GridRenderer<impl Fn(GridPosition) -> Color + '_> {
+        GridRenderer::new(self.rows, self.columns, |pos| self.get(pos))
+    }
+    /// Draws the frame with 24 bit ANSI colors, packing two rows into every line of text with
+    /// upper half blocks so the grid keeps its aspect ratio.
+    pub fn write_ansi(&self, mut writer: impl Write) -> std::io::Result<()> {
+        for row in (0..self.rows).step_by(2) {
+            for column in 0..self.columns {
+                let top = self.get((row, column));
+                let bottom = match row + 1 < self.rows {
+                    true => self.get((row + 1, column)),
+                    false => Color::BLACK,
+                };
+                write!(
+                    writer,
+                    "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m▀",
+                    top.r, top.g, top.b, bottom.r, bottom.g, bottom.b
+                )?;
+            }
+            writeln!(writer, "\x1b[0m")?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects the [`Frame`]s of a simulation.
+#[derive(Clone, Debug, Default)]
+pub struct FrameRecorder {
+    frames: Vec<Frame>,
+}
+impl FrameRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Snapshots the current state, skipping it if nothing changed since the last frame.
+    pub fn record(&mut self, simulation: &impl Simulate) {
+        let frame = Frame::capture(simulation);
+        if self.frames.last() != Some(&frame) {
+            self.frames.push(frame);
+        }
+    }
+    pub fn frames(&self) -> &[Frame] {
+        &self.frames
+    }
+    /// Redraws every frame in place, showing `fps` frames per second.
+    pub fn play(&self, mut writer: impl Write, fps: u32) -> std::io::Result<()> {
+        let frame_time = Duration::from_secs(1) / fps;
+        write!(writer, "\x1b[2J")?;
+        for frame in &self.frames {
+            let start = Instant::now();
+            write!(writer, "\x1b[H")?;
+            frame.write_ansi(&mut writer)?;
+            writer.flush()?;
+            std::thread::sleep(frame_time.saturating_sub(start.elapsed()));
+        }
+        Ok(())
+    }
+    /// Saves every frame as `frame_00000.ppm`, `frame_00001.ppm`, … into `dir`.
+    pub fn save_frames(&self, dir: impl AsRef<Path>) -> std::io::Result<()> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            frame
+                .renderer()
+                .save(dir.join(format!("frame_{i:05}.ppm")))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+/// Fills a single row from left to right, one cell per step.
+struct Fill {
+    filled: usize,
+    columns: usize,
+}
+#[cfg(test)]
+impl Simulate for Fill {
+    fn step(&mut self) -> bool {
+        self.filled = (self.filled + 1).min(self.columns);
+        self.filled < self.columns
+    }
+    fn dimensions(&self) -> (usize, usize) {
+        (1, self.columns)
+    }
+    fn color(&self, (_, column): GridPosition) -> Color {
+        match column < self.filled {
+            true => Color::WHITE,
+            false => Color::BLACK,
+        }
+    }
+}
+
+#[test]
+fn test_frame_recorder() {
+    let mut fill = Fill {
+        filled: 0,
+        columns: 3,
+    };
+    let mut recorder = FrameRecorder::new();
+    recorder.record(&fill);
+    while fill.step() {
+        recorder.record(&fill);
+    }
+    recorder.record(&fill);
+    assert_eq!(recorder.frames().len(), 4);
+    assert_eq!(recorder.frames()[1].get((0, 0)), Color::WHITE);
+    assert_eq!(recorder.frames()[1].get((0, 1)), Color::BLACK);
+
+    let mut ansi = Vec::new();
+    recorder.play(&mut ansi, 1000).unwrap();
+    let ansi = String::from_utf8(ansi).unwrap();
+    assert_eq!(ansi.matches("\x1b[H").count(), 4);
+    assert_eq!(ansi.matches('▀').count(), 12);
+
+    let dir = std::env::temp_dir().join(format!("aoc24_frames_{}", std::process::id()));
+    recorder.save_frames(&dir).unwrap();
+    assert!(dir.join("frame_00003.ppm").exists());
+    assert!(!dir.join("frame_00004.ppm").exists());
+    std::fs::remove_dir_all(dir).unwrap();
+}
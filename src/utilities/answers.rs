@@ -0,0 +1,158 @@
+//! A store of verified answers, keyed by the day and the hash of the input.
+//!
+//! Everyone on the team has a different input, so the answers can not be hardcoded like in the
+//! day tests. The store is a text file with one input per line: the day, the hash and the
+//! answers of both parts, e.g. `7 1f3a9c0e5b7d2468 3749 11387`. Empty lines and lines starting
+//! with `#` are ignored.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    hash::Hasher,
+    io::{ErrorKind, Write},
+    path::Path,
+};
+
+use super::{
+    hash::FxHasher,
+    input::{Input, ParseError},
+};
+
+/// Hashes the input without its line endings and trailing whitespace, so the same input saved
+/// on another platform has the same hash.
+pub fn input_hash(input: &str) -> u64 {
+    let mut hasher = FxHasher::default();
+    hasher.write(input.normalized().as_bytes());
+    hasher.finish()
+}
+
+/// How answers compare to the ones in the store.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    /// The store has different answers for the input.
+    Fail {
+        expected: Vec<String>,
+    },
+    /// The store has no answers for the input yet.
+    Unknown,
+}
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pass => f.write_str("pass"),
+            Self::Fail { expected } => {
+                f.write_fmt(format_args!("FAIL, expected {}", expected.join(", ")))
+            }
+            Self::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnswerStore {
+    answers: BTreeMap<(u8, u64), Vec<String>>,
+}
+impl AnswerStore {
+    /// Reads the store at `path`, which is empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => text
+                .parse()
+                .map_err(|error| std::io::Error::new(ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+    /// Writes the store to `path`, sorted by day and hash.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        write!(file, "{self}")?;
+        file.flush()
+    }
+    pub fn get(&self, day: u8, hash: u64) -> Option<&[String]> {
+        self.answers.get(&(day, hash)).map(Vec::as_slice)
+    }
+    pub fn insert(&mut self, day: u8, hash: u64, answers: Vec<String>) {
+        self.answers.insert((day, hash), answers);
+    }
+    /// Compares `answers` with the stored answers for the input with `hash`.
+    pub fn verdict(&self, day: u8, hash: u64, answers: &[String]) -> Verdict {
+        match self.get(day, hash) {
+            Some(expected) if expected == answers => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_vec(),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+impl std::str::FromStr for AnswerStore {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut store = Self::default();
+        for (line, text) in s.numbered_lines() {
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+            let in_line = |error: ParseError| ParseError::Line(line, Box::new(error));
+            let mut fields = text.split_whitespace();
+            let day = fields
+                .next()
+                .unwrap_or_default()
+                .parse()
+                .map_err(|e| in_line(ParseError::Int(e)))?;
+            let hash = fields
+                .next()
+                .ok_or(in_line(ParseError::Missing("the input hash")))?;
+            let hash = u64::from_str_radix(hash, 16).map_err(|e| in_line(ParseError::Int(e)))?;
+            let answers: Vec<String> = fields.map(str::to_string).collect();
+            if answers.is_empty() {
+                return Err(in_line(ParseError::Missing("the answers")));
+            }
+            store.insert(day, hash, answers);
+        }
+        Ok(store)
+    }
+}
+impl Display for AnswerStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for ((day, hash), answers) in &self.answers {
+            writeln!(f, "{day} {hash:016x} {}", answers.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_input_hash() {
+    assert_eq!(input_hash("1 2\r\n3 4\r\n"), input_hash("1 2\n3 4"));
+    assert_ne!(input_hash("1 2\n3 4"), input_hash("1 2\n3 5"));
+}
+#[test]
+fn test_store() {
+    let text = "# verified answers\n7 00000000000000ff 3749 11387\n\n1 0000000000000001 11 31\n";
+    let mut store: AnswerStore = text.parse().unwrap();
+    let answers = |a: &[&str]| -> Vec<String> { a.iter().map(|s| s.to_string()).collect() };
+    assert_eq!(
+        store.verdict(7, 255, &answers(&["3749", "11387"])),
+        Verdict::Pass
+    );
+    assert_eq!(
+        store.verdict(1, 1, &answers(&["11", "30"])).to_string(),
+        "FAIL, expected 11, 31"
+    );
+    assert_eq!(
+        store.verdict(1, 2, &answers(&["11", "31"])),
+        Verdict::Unknown
+    );
+    store.insert(1, 2, answers(&["5"]));
+    assert_eq!(
+        store.to_string(),
+        "1 0000000000000001 11 31\n1 0000000000000002 5\n7 00000000000000ff 3749 11387\n"
+    );
+    assert_eq!(store.to_string().parse(), Ok(store));
+    let error = "7 00ff\n".parse::<AnswerStore>().unwrap_err();
+    assert_eq!(error.to_string(), "line 1: missing the answers");
+}
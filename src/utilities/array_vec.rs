@@ -0,0 +1,199 @@
+use std::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
+
+/// A vector with a fixed capacity of `N` elements that lives on the stack.
+///
+/// Unused slots are filled with `T::default()`, which keeps the implementation free of unsafe code
+/// and restricts it to small `Copy` types like coordinates.
+#[derive(Clone, Copy)]
+pub struct ArrayVec<T, const N: usize> {
+    data: [T; N],
+    len: usize,
+}
+
+impl<T, const N: usize> Default for ArrayVec<T, N>
+where
+    T: Copy + Default,
+{
+    #[inline]
+    fn default() -> Self {
+        Self {
+            data: [T::default(); N],
+            len: 0,
+        }
+    }
+}
+
+impl<T, const N: usize> ArrayVec<T, N>
+where
+    T: Copy + Default,
+{
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+    /// Appends `value` to the end.
+    ///
+    /// # Panics
+    /// If the vector is already full.
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        if self.try_push(value).is_err() {
+            panic!("The ArrayVec is already full with {N} elements");
+        }
+    }
+    /// Appends `value` to the end.
+    ///
+    /// # Returns
+    /// `Err(value)` if the vector is already full.
+    #[inline]
+    pub fn try_push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.data[self.len] = value;
+        self.len += 1;
+        Ok(())
+    }
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        Some(self.data[self.len])
+    }
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data[..self.len]
+    }
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.data[..self.len]
+    }
+}
+
+impl<T, const N: usize> Deref for ArrayVec<T, N>
+where
+    T: Copy + Default,
+{
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+impl<T, const N: usize> DerefMut for ArrayVec<T, N>
+where
+    T: Copy + Default,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T, const N: usize> Debug for ArrayVec<T, N>
+where
+    T: Copy + Default + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+impl<T, const N: usize> PartialEq for ArrayVec<T, N>
+where
+    T: Copy + Default + PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+impl<T, const N: usize> Eq for ArrayVec<T, N> where T: Copy + Default + Eq {}
+
+impl<T, const N: usize> Extend<T> for ArrayVec<T, N>
+where
+    T: Copy + Default,
+{
+    /// # Panics
+    /// If the iterator yields more elements than there is space left.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+impl<T, const N: usize> FromIterator<T> for ArrayVec<T, N>
+where
+    T: Copy + Default,
+{
+    /// # Panics
+    /// If the iterator yields more than `N` elements.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = Self::new();
+        result.extend(iter);
+        result
+    }
+}
+
+impl<T, const N: usize> IntoIterator for ArrayVec<T, N>
+where
+    T: Copy + Default,
+{
+    type Item = T;
+    type IntoIter = std::iter::Take<std::array::IntoIter<T, N>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter().take(self.len)
+    }
+}
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayVec<T, N>
+where
+    T: Copy + Default,
+{
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[test]
+fn test_array_vec() {
+    let mut list: ArrayVec<(usize, usize), 4> = ArrayVec::new();
+    assert!(list.is_empty());
+    list.push((1, 2));
+    list.push((3, 4));
+    assert_eq!(list.len(), 2);
+    assert_eq!(list[1], (3, 4));
+    assert_eq!(list.pop(), Some((3, 4)));
+    list.extend([(5, 6), (7, 8), (9, 10)]);
+    assert!(list.is_full());
+    assert_eq!(list.try_push((0, 0)), Err((0, 0)));
+    assert_eq!(
+        list.into_iter().collect::<Vec<_>>(),
+        vec![(1, 2), (5, 6), (7, 8), (9, 10)]
+    );
+    let evens: ArrayVec<u8, 4> = (0..8).filter(|v| v % 2 == 0).collect();
+    assert_eq!(evens.as_slice(), &[0, 2, 4, 6]);
+    assert_eq!(format!("{evens:?}"), "[0, 2, 4, 6]");
+}
@@ -0,0 +1,355 @@
+use std::ops::{BitAnd, BitOr, Sub};
+
+use super::grid::GridPosition;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A fixed capacity set of `usize` values stored as packed bits.
+///
+/// The number of set bits is tracked on every modification, so [`BitSet::len`] is `O(1)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitSet {
+    words: Vec<u64>,
+    capacity: usize,
+    len: usize,
+}
+
+impl BitSet {
+    /// Creates an empty set that can hold the values `0..capacity`.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(WORD_BITS)],
+            capacity,
+            len: 0,
+        }
+    }
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    #[inline(always)]
+    fn locate(&self, index: usize) -> (usize, u64) {
+        assert!(
+            index < self.capacity,
+            "The index {index} is out of the bitset capacity {}",
+            self.capacity
+        );
+        (index / WORD_BITS, 1 << (index % WORD_BITS))
+    }
+    /// Inserts `index` into the set.
+    ///
+    /// # Returns
+    /// `true` if the value was not present before.
+    #[inline]
+    pub fn insert(&mut self, index: usize) -> bool {
+        let (word, mask) = self.locate(index);
+        let inserted = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        self.len += inserted as usize;
+        inserted
+    }
+    /// Removes `index` from the set.
+    ///
+    /// # Returns
+    /// `true` if the value was present before.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> bool {
+        let (word, mask) = self.locate(index);
+        let removed = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        self.len -= removed as usize;
+        removed
+    }
+    #[inline]
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.capacity && self.words[index / WORD_BITS] & (1 << (index % WORD_BITS)) != 0
+    }
+    /// Removes all values while keeping the allocation.
+    ///
+    /// Clearing an already empty set does not touch the memory at all.
+    #[inline]
+    pub fn clear(&mut self) {
+        if self.len != 0 {
+            self.words.fill(0);
+            self.len = 0;
+        }
+    }
+    fn combine_with(&mut self, other: &Self, operation: fn(u64, u64) -> u64) {
+        assert_eq!(
+            self.capacity, other.capacity,
+            "Only bitsets with the same capacity can be combined"
+        );
+        self.len = 0;
+        for (word, &other_word) in self.words.iter_mut().zip(&other.words) {
+            *word = operation(*word, other_word);
+            self.len += word.count_ones() as usize;
+        }
+    }
+    /// Adds every value of `other` to `self`.
+    #[inline]
+    pub fn union_with(&mut self, other: &Self) {
+        self.combine_with(other, |a, b| a | b);
+    }
+    /// Keeps only the values that are also contained in `other`.
+    #[inline]
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.combine_with(other, |a, b| a & b);
+    }
+    /// Removes every value of `other` from `self`.
+    #[inline]
+    pub fn difference_with(&mut self, other: &Self) {
+        self.combine_with(other, |a, b| a & !b);
+    }
+    /// Iterates over all set values in ascending order.
+    #[inline]
+    pub fn iter(&self) -> BitSetIter<'_> {
+        BitSetIter {
+            words: &self.words,
+            word_index: 0,
+            current: self.words.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl BitOr for &BitSet {
+    type Output = BitSet;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        let mut result = self.clone();
+        result.union_with(rhs);
+        result
+    }
+}
+impl BitAnd for &BitSet {
+    type Output = BitSet;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        let mut result = self.clone();
+        result.intersect_with(rhs);
+        result
+    }
+}
+impl Sub for &BitSet {
+    type Output = BitSet;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut result = self.clone();
+        result.difference_with(rhs);
+        result
+    }
+}
+
+pub struct BitSetIter<'a> {
+    words: &'a [u64],
+    word_index: usize,
+    current: u64,
+}
+impl Iterator for BitSetIter<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current == 0 {
+            self.word_index += 1;
+            self.current = *self.words.get(self.word_index)?;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        Some(self.word_index * WORD_BITS + bit)
+    }
+}
+impl<'a> IntoIterator for &'a BitSet {
+    type Item = usize;
+    type IntoIter = BitSetIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// A two dimensional [`BitSet`] where every cell can additionally hold `STATES` independent bits.
+///
+/// With the default of a single state the grid behaves like a set of positions. More states can be
+/// used to track e.g. the direction a cell was entered from, where every `(position, state)` pair
+/// is its own member of the set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitGrid<const STATES: usize = 1> {
+    bits: BitSet,
+    rows: usize,
+    columns: usize,
+}
+
+impl<const STATES: usize> BitGrid<STATES> {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Self {
+            bits: BitSet::new(rows * columns * STATES),
+            rows,
+            columns,
+        }
+    }
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+    #[inline]
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+    #[inline(always)]
+    fn index(&self, (row, column): GridPosition, state: usize) -> usize {
+        assert!(
+            row < self.rows && column < self.columns && state < STATES,
+            "The position ({row}, {column}) with state {state} is outside of the grid"
+        );
+        (row * self.columns + column) * STATES + state
+    }
+    #[inline(always)]
+    fn position(&self, index: usize) -> (GridPosition, usize) {
+        let cell = index / STATES;
+        ((cell / self.columns, cell % self.columns), index % STATES)
+    }
+    /// Returns `true` if the `(position, state)` pair was not present before.
+    #[inline]
+    pub fn insert_state(&mut self, position: GridPosition, state: usize) -> bool {
+        let index = self.index(position, state);
+        self.bits.insert(index)
+    }
+    /// Returns `true` if the `(position, state)` pair was present before.
+    #[inline]
+    pub fn remove_state(&mut self, position: GridPosition, state: usize) -> bool {
+        let index = self.index(position, state);
+        self.bits.remove(index)
+    }
+    /// Returns `false` for positions outside of the grid.
+    #[inline]
+    pub fn contains_state(&self, (row, column): GridPosition, state: usize) -> bool {
+        row < self.rows
+            && column < self.columns
+            && state < STATES
+            && self.bits.contains(self.index((row, column), state))
+    }
+    /// Returns `true` if any state of the position is set.
+    #[inline]
+    pub fn contains_any(&self, position: GridPosition) -> bool {
+        (0..STATES).any(|state| self.contains_state(position, state))
+    }
+    /// Returns the number of set `(position, state)` pairs.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+    #[inline]
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+    #[inline]
+    pub fn union_with(&mut self, other: &Self) {
+        self.bits.union_with(&other.bits);
+    }
+    #[inline]
+    pub fn intersect_with(&mut self, other: &Self) {
+        self.bits.intersect_with(&other.bits);
+    }
+    #[inline]
+    pub fn difference_with(&mut self, other: &Self) {
+        self.bits.difference_with(&other.bits);
+    }
+    /// Iterates over all set `(position, state)` pairs in row major order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (GridPosition, usize)> + '_ {
+        self.bits.iter().map(|index| self.position(index))
+    }
+    /// Iterates over every position that has at least one state set, in row major order.
+    #[inline]
+    pub fn positions(&self) -> impl Iterator<Item = GridPosition> + '_ {
+        let mut last = None;
+        self.bits
+            .iter()
+            .map(|index| index / STATES)
+            .filter(move |&cell| last.replace(cell) != Some(cell))
+            .map(|cell| (cell / self.columns, cell % self.columns))
+    }
+}
+
+impl BitGrid {
+    #[inline]
+    pub fn insert(&mut self, position: GridPosition) -> bool {
+        self.insert_state(position, 0)
+    }
+    #[inline]
+    pub fn remove(&mut self, position: GridPosition) -> bool {
+        self.remove_state(position, 0)
+    }
+    #[inline]
+    pub fn contains(&self, position: GridPosition) -> bool {
+        self.contains_state(position, 0)
+    }
+}
+
+#[test]
+fn test_bitset() {
+    let mut set = BitSet::new(200);
+    assert!(set.insert(3));
+    assert!(set.insert(64));
+    assert!(set.insert(199));
+    assert!(!set.insert(64));
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(199));
+    assert!(!set.contains(200));
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 64, 199]);
+    assert!(set.remove(3));
+    assert!(!set.remove(3));
+    assert_eq!(set.len(), 2);
+    set.clear();
+    assert!(set.is_empty());
+    assert_eq!(set.iter().next(), None);
+}
+#[test]
+fn test_bitset_set_operations() {
+    let mut a = BitSet::new(130);
+    let mut b = BitSet::new(130);
+    [1, 2, 70, 129].into_iter().for_each(|v| _ = a.insert(v));
+    [2, 3, 129].into_iter().for_each(|v| _ = b.insert(v));
+    assert_eq!((&a | &b).iter().collect::<Vec<_>>(), vec![1, 2, 3, 70, 129]);
+    assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![2, 129]);
+    assert_eq!((&a - &b).iter().collect::<Vec<_>>(), vec![1, 70]);
+    a.intersect_with(&b);
+    assert_eq!(a.len(), 2);
+}
+#[test]
+fn test_bit_grid_states() {
+    let mut grid = BitGrid::<4>::new(3, 5);
+    assert!(grid.insert_state((1, 4), 2));
+    assert!(grid.insert_state((1, 4), 3));
+    assert!(grid.insert_state((2, 0), 0));
+    assert!(!grid.insert_state((1, 4), 2));
+    assert_eq!(grid.len(), 3);
+    assert!(grid.contains_any((1, 4)));
+    assert!(!grid.contains_state((1, 4), 0));
+    assert!(!grid.contains_any((7, 7)));
+    assert_eq!(
+        grid.iter().collect::<Vec<_>>(),
+        vec![((1, 4), 2), ((1, 4), 3), ((2, 0), 0)]
+    );
+    assert_eq!(grid.positions().collect::<Vec<_>>(), vec![(1, 4), (2, 0)]);
+    let mut positions = BitGrid::new(2, 2);
+    positions.insert((1, 1));
+    assert!(positions.contains((1, 1)));
+    assert_eq!(positions.len(), 1);
+}
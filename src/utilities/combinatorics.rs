@@ -0,0 +1,406 @@
+//! Combinatorial iterator adapters.
+//!
+//! The adapters collect the source iterator once, because they walk over it many times, and then
+//! only keep indices into it. As iterators they return a new `Vec` for every combination; loops
+//! over many combinations can reuse a single buffer with `next_into` instead.
+
+pub trait IteratorCombinatorics: Iterator
+where
+    Self: Sized,
+    Self::Item: Clone,
+{
+    /// Iterates over every unordered pair `(a, b)` where `a` comes before `b` in the iterator.
+    #[inline]
+    fn pairs(self) -> Pairs<Self::Item> {
+        Pairs::new(self.collect(), false)
+    }
+    /// Iterates over every ordered pair `(a, b)` of two different positions in the iterator, so
+    /// every unordered pair is returned in both directions.
+    #[inline]
+    fn ordered_pairs(self) -> Pairs<Self::Item> {
+        Pairs::new(self.collect(), true)
+    }
+    /// Iterates over all `k` element subsets in lexicographic order of their positions.
+    #[inline]
+    fn combinations(self, k: usize) -> Combinations<Self::Item> {
+        Combinations::new(self.collect(), k)
+    }
+    /// Iterates over all ordered selections of `k` elements at different positions.
+    #[inline]
+    fn permutations(self, k: usize) -> Permutations<Self::Item> {
+        Permutations::new(self.collect(), k)
+    }
+    /// Iterates over all `k` long sequences where every element can be chosen from the iterator,
+    /// e.g. every way to fill `k` gaps with one of the operators.
+    #[inline]
+    fn cartesian_power(self, k: usize) -> CartesianPower<Self::Item> {
+        CartesianPower::new(self.collect(), k)
+    }
+    /// Lazily iterates over all subsets, starting with the empty one.
+    ///
+    /// # Panics
+    /// If the iterator has more than 63 elements, because the subsets could never be exhausted.
+    #[inline]
+    fn power_set(self) -> PowerSet<Self::Item> {
+        PowerSet::new(self.collect())
+    }
+}
+
+impl<I> IteratorCombinatorics for I
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+}
+
+pub struct Pairs<T> {
+    pool: Vec<T>,
+    ordered: bool,
+    first: usize,
+    second: usize,
+}
+impl<T> Pairs<T> {
+    fn new(pool: Vec<T>, ordered: bool) -> Self {
+        Self {
+            pool,
+            ordered,
+            first: 0,
+            second: if ordered { 0 } else { 1 },
+        }
+    }
+}
+impl<T> Iterator for Pairs<T>
+where
+    T: Clone,
+{
+    type Item = (T, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.first >= self.pool.len() {
+                return None;
+            }
+            if self.second >= self.pool.len() {
+                self.first += 1;
+                self.second = if self.ordered { 0 } else { self.first + 1 };
+                continue;
+            }
+            let second = self.second;
+            self.second += 1;
+            if second != self.first {
+                return Some((self.pool[self.first].clone(), self.pool[second].clone()));
+            }
+        }
+    }
+}
+
+pub struct Combinations<T> {
+    pool: Vec<T>,
+    indices: Vec<usize>,
+    done: bool,
+}
+impl<T> Combinations<T> {
+    fn new(pool: Vec<T>, k: usize) -> Self {
+        let done = k > pool.len();
+        Self {
+            pool,
+            indices: (0..k).collect(),
+            done,
+        }
+    }
+}
+impl<T> Combinations<T>
+where
+    T: Clone,
+{
+    /// Writes the next combination into `buffer` instead of allocating a new `Vec`.
+    ///
+    /// # Returns
+    /// `false` once every combination was returned, leaving `buffer` untouched.
+    pub fn next_into(&mut self, buffer: &mut Vec<T>) -> bool {
+        if self.done {
+            return false;
+        }
+        buffer.clear();
+        buffer.extend(self.indices.iter().map(|&i| self.pool[i].clone()));
+        let (n, k) = (self.pool.len(), self.indices.len());
+        match (0..k).rev().find(|&i| self.indices[i] != i + n - k) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in i + 1..k {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+        true
+    }
+}
+impl<T> Iterator for Combinations<T>
+where
+    T: Clone,
+{
+    type Item = Vec<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut result = Vec::with_capacity(self.indices.len());
+        self.next_into(&mut result).then_some(result)
+    }
+}
+
+pub struct Permutations<T> {
+    pool: Vec<T>,
+    indices: Vec<usize>,
+    cycles: Vec<usize>,
+    k: usize,
+    done: bool,
+}
+impl<T> Permutations<T> {
+    fn new(pool: Vec<T>, k: usize) -> Self {
+        let n = pool.len();
+        Self {
+            indices: (0..n).collect(),
+            cycles: (0..k.min(n)).map(|i| n - i).collect(),
+            pool,
+            k,
+            done: k > n,
+        }
+    }
+}
+impl<T> Permutations<T>
+where
+    T: Clone,
+{
+    /// Writes the next permutation into `buffer` instead of allocating a new `Vec`.
+    ///
+    /// # Returns
+    /// `false` once every permutation was returned, leaving `buffer` untouched.
+    pub fn next_into(&mut self, buffer: &mut Vec<T>) -> bool {
+        if self.done {
+            return false;
+        }
+        buffer.clear();
+        buffer.extend(self.indices[..self.k].iter().map(|&i| self.pool[i].clone()));
+        // advance like Python's itertools.permutations
+        let n = self.pool.len();
+        self.done = true;
+        for i in (0..self.k).rev() {
+            self.cycles[i] -= 1;
+            if self.cycles[i] == 0 {
+                self.indices[i..].rotate_left(1);
+                self.cycles[i] = n - i;
+            } else {
+                let j = n - self.cycles[i];
+                self.indices.swap(i, j);
+                self.done = false;
+                break;
+            }
+        }
+        true
+    }
+}
+impl<T> Iterator for Permutations<T>
+where
+    T: Clone,
+{
+    type Item = Vec<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut result = Vec::with_capacity(self.k);
+        self.next_into(&mut result).then_some(result)
+    }
+}
+
+pub struct CartesianPower<T> {
+    pool: Vec<T>,
+    indices: Vec<usize>,
+    done: bool,
+}
+impl<T> CartesianPower<T> {
+    fn new(pool: Vec<T>, k: usize) -> Self {
+        let done = pool.is_empty() && k > 0;
+        Self {
+            pool,
+            indices: vec![0; k],
+            done,
+        }
+    }
+}
+impl<T> CartesianPower<T>
+where
+    T: Clone,
+{
+    /// Writes the next sequence into `buffer` instead of allocating a new `Vec`.
+    ///
+    /// # Returns
+    /// `false` once every sequence was returned, leaving `buffer` untouched.
+    pub fn next_into(&mut self, buffer: &mut Vec<T>) -> bool {
+        if self.done {
+            return false;
+        }
+        buffer.clear();
+        buffer.extend(self.indices.iter().map(|&i| self.pool[i].clone()));
+        self.done = true;
+        for index in self.indices.iter_mut().rev() {
+            *index += 1;
+            if *index < self.pool.len() {
+                self.done = false;
+                break;
+            }
+            *index = 0;
+        }
+        true
+    }
+}
+impl<T> Iterator for CartesianPower<T>
+where
+    T: Clone,
+{
+    type Item = Vec<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut result = Vec::with_capacity(self.indices.len());
+        self.next_into(&mut result).then_some(result)
+    }
+}
+
+pub struct PowerSet<T> {
+    pool: Vec<T>,
+    mask: u64,
+    end: u64,
+}
+impl<T> PowerSet<T> {
+    fn new(pool: Vec<T>) -> Self {
+        assert!(
+            pool.len() < 64,
+            "The power set of more than 63 elements can not be iterated"
+        );
+        Self {
+            end: 1 << pool.len(),
+            pool,
+            mask: 0,
+        }
+    }
+}
+impl<T> PowerSet<T>
+where
+    T: Clone,
+{
+    /// Writes the next subset into `buffer` instead of allocating a new `Vec`.
+    ///
+    /// # Returns
+    /// `false` once every subset was returned, leaving `buffer` untouched.
+    pub fn next_into(&mut self, buffer: &mut Vec<T>) -> bool {
+        if self.mask >= self.end {
+            return false;
+        }
+        buffer.clear();
+        buffer.extend(
+            self.pool
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| self.mask & (1 << i) != 0)
+                .map(|(_, value)| value.clone()),
+        );
+        self.mask += 1;
+        true
+    }
+}
+impl<T> Iterator for PowerSet<T>
+where
+    T: Clone,
+{
+    type Item = Vec<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut result = Vec::with_capacity(self.mask.count_ones() as usize);
+        self.next_into(&mut result).then_some(result)
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end - self.mask) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+#[test]
+fn test_pairs() {
+    let pairs: Vec<_> = [1, 2, 3].into_iter().pairs().collect();
+    assert_eq!(pairs, vec![(1, 2), (1, 3), (2, 3)]);
+    let ordered: Vec<_> = [1, 2, 3].into_iter().ordered_pairs().collect();
+    assert_eq!(
+        ordered,
+        vec![(1, 2), (1, 3), (2, 1), (2, 3), (3, 1), (3, 2)]
+    );
+    assert_eq!([1].into_iter().pairs().next(), None);
+}
+#[test]
+fn test_combinations_and_permutations() {
+    let combinations: Vec<_> = "abcd".chars().combinations(2).collect();
+    assert_eq!(combinations.len(), 6);
+    assert_eq!(combinations[0], vec!['a', 'b']);
+    assert_eq!(combinations[5], vec!['c', 'd']);
+    assert_eq!("ab".chars().combinations(3).count(), 0);
+    assert_eq!("ab".chars().combinations(0).count(), 1);
+    let permutations: Vec<_> = [1, 2, 3].into_iter().permutations(3).collect();
+    assert_eq!(
+        permutations,
+        vec![
+            vec![1, 2, 3],
+            vec![1, 3, 2],
+            vec![2, 1, 3],
+            vec![2, 3, 1],
+            vec![3, 1, 2],
+            vec![3, 2, 1]
+        ]
+    );
+    assert_eq!((0..5).permutations(2).count(), 20);
+    assert_eq!((0..2).permutations(3).count(), 0);
+}
+#[test]
+fn test_cartesian_power_and_power_set() {
+    let operators: Vec<_> = ['+', '*'].into_iter().cartesian_power(2).collect();
+    assert_eq!(
+        operators,
+        vec![
+            vec!['+', '+'],
+            vec!['+', '*'],
+            vec!['*', '+'],
+            vec!['*', '*']
+        ]
+    );
+    assert_eq!((0..3).cartesian_power(4).count(), 81);
+    assert_eq!((0..3).cartesian_power(0).count(), 1);
+    let subsets: Vec<_> = [1, 2].into_iter().power_set().collect();
+    assert_eq!(subsets, vec![vec![], vec![1], vec![2], vec![1, 2]]);
+}
+#[test]
+fn test_next_into() {
+    let mut combinations = (0..4).combinations(2);
+    let mut buffer = Vec::new();
+    let mut count = 0;
+    while combinations.next_into(&mut buffer) {
+        assert_eq!(buffer.len(), 2);
+        count += 1;
+    }
+    assert_eq!(count, 6);
+    assert_eq!(buffer, vec![2, 3]);
+    let mut operators = ['+', '*'].into_iter().cartesian_power(3);
+    let mut sequence = Vec::new();
+    assert!(operators.next_into(&mut sequence));
+    let capacity = sequence.capacity();
+    while operators.next_into(&mut sequence) {
+        assert_eq!(sequence.capacity(), capacity);
+    }
+    assert_eq!(sequence, vec!['*', '*', '*']);
+    let mut permutations = (0..3).permutations(2);
+    assert!(permutations.next_into(&mut buffer));
+    assert_eq!(buffer, vec![0, 1]);
+    let mut subsets = [1, 2].into_iter().power_set();
+    assert_eq!(subsets.by_ref().nth(3), Some(vec![1, 2]));
+    assert!(!subsets.next_into(&mut buffer));
+}
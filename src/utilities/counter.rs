@@ -0,0 +1,209 @@
+use std::{
+    cmp::Reverse,
+    collections::hash_map,
+    hash::Hash,
+    iter::Sum,
+    num::ZeroablePrimitive,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+use super::FastHashMap;
+
+/// A multiset that stores how often every key was added.
+///
+/// Counters can be merged with `+=` and subtracted with `-=`, where subtracting drops every key
+/// whose count would not stay positive.
+///
+/// The count type `C` can be chosen to match the expected multiplicities, e.g. `u128` for counts
+/// that grow exponentially.
+#[derive(Clone, Debug)]
+pub struct Counter<K, C = usize>
+where
+    C: ZeroablePrimitive,
+{
+    counts: FastHashMap<K, C>,
+}
+
+impl<K, C> Default for Counter<K, C>
+where
+    C: ZeroablePrimitive,
+{
+    #[inline]
+    fn default() -> Self {
+        Self {
+            counts: FastHashMap::default(),
+        }
+    }
+}
+
+impl<K, C> Counter<K, C>
+where
+    K: Hash + Eq,
+    C: ZeroablePrimitive + From<u8> + Copy + Ord + Add<Output = C> + Sub<Output = C>,
+{
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds `key` `amount` times.
+    #[inline]
+    pub fn add(&mut self, key: K, amount: C) {
+        if amount == 0u8.into() {
+            return;
+        }
+        let count = self.counts.entry(key).or_insert(0u8.into());
+        *count = *count + amount;
+    }
+    #[inline]
+    pub fn increment(&mut self, key: K) {
+        self.add(key, 1u8.into());
+    }
+    /// Adds every `(key, amount)` pair of the iterator.
+    pub fn add_all<I: IntoIterator<Item = (K, C)>>(&mut self, iter: I) {
+        for (key, amount) in iter {
+            self.add(key, amount);
+        }
+    }
+    /// Returns how often `key` was added, which is zero for unknown keys.
+    #[inline]
+    pub fn get(&self, key: &K) -> C {
+        self.counts.get(key).copied().unwrap_or(0u8.into())
+    }
+    #[inline]
+    pub fn remove(&mut self, key: &K) -> Option<C> {
+        self.counts.remove(key)
+    }
+    /// Returns the number of distinct keys.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+    /// Sums all counts in the width `W`, which can be wider than the count type to avoid overflows.
+    #[inline]
+    pub fn total<W>(&self) -> W
+    where
+        W: From<C> + Sum<W>,
+    {
+        self.counts.values().map(|&count| W::from(count)).sum()
+    }
+    /// Iterates over every key together with its count in arbitrary order.
+    #[inline]
+    pub fn entries(&self) -> impl Iterator<Item = (&K, C)> {
+        self.counts.iter().map(|(key, &count)| (key, count))
+    }
+    /// Returns the `n` keys with the highest counts, ordered from the most to the least common.
+    pub fn most_common(&self, n: usize) -> Vec<(&K, C)> {
+        let mut entries: Vec<(&K, C)> = self.entries().collect();
+        entries.sort_unstable_by_key(|&(_, count)| Reverse(count));
+        entries.truncate(n);
+        entries
+    }
+}
+
+impl<K, C> FromIterator<K> for Counter<K, C>
+where
+    K: Hash + Eq,
+    C: ZeroablePrimitive + From<u8> + Copy + Ord + Add<Output = C> + Sub<Output = C>,
+{
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+impl<K, C> Extend<K> for Counter<K, C>
+where
+    K: Hash + Eq,
+    C: ZeroablePrimitive + From<u8> + Copy + Ord + Add<Output = C> + Sub<Output = C>,
+{
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            self.increment(key);
+        }
+    }
+}
+impl<K, C> IntoIterator for Counter<K, C>
+where
+    C: ZeroablePrimitive,
+{
+    type Item = (K, C);
+    type IntoIter = hash_map::IntoIter<K, C>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+impl<K, C> PartialEq for Counter<K, C>
+where
+    K: Hash + Eq,
+    C: ZeroablePrimitive + PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<K, C> AddAssign for Counter<K, C>
+where
+    K: Hash + Eq,
+    C: ZeroablePrimitive + From<u8> + Copy + Ord + Add<Output = C> + Sub<Output = C>,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.add_all(rhs);
+    }
+}
+impl<K, C> SubAssign for Counter<K, C>
+where
+    K: Hash + Eq,
+    C: ZeroablePrimitive + From<u8> + Copy + Ord + Add<Output = C> + Sub<Output = C>,
+{
+    /// Subtracts the counts of `rhs`, removing every key whose count would drop to zero or below.
+    fn sub_assign(&mut self, rhs: Self) {
+        for (key, amount) in rhs {
+            if let hash_map::Entry::Occupied(mut entry) = self.counts.entry(key) {
+                if *entry.get() > amount {
+                    *entry.get_mut() = *entry.get() - amount;
+                } else {
+                    entry.remove();
+                }
+            }
+        }
+    }
+}
+#[test]
+fn test_counter() {
+    let mut counter: Counter<char> = "abracadabra".chars().collect();
+    assert_eq!(counter.get(&'a'), 5);
+    assert_eq!(counter.get(&'z'), 0);
+    assert_eq!(counter.len(), 5);
+    assert_eq!(counter.total::<usize>(), 11);
+    let most_common = counter.most_common(3);
+    assert_eq!(most_common[0], (&'a', 5));
+    assert_eq!(most_common[1].1, 2);
+    assert_eq!(most_common.len(), 3);
+    counter.add('z', 7);
+    assert_eq!(counter.most_common(1), vec![(&'z', 7)]);
+    assert_eq!(counter.get(&'z'), 7);
+}
+#[test]
+fn test_counter_arithmetic() {
+    let a: Counter<u8, u64> = [1, 1, 2, 3].into_iter().collect();
+    let b: Counter<u8, u64> = [1, 3, 3, 4].into_iter().collect();
+    let mut sum = a.clone();
+    sum += b.clone();
+    assert_eq!(sum.get(&1), 3);
+    assert_eq!(sum.get(&3), 3);
+    assert_eq!(sum.total::<u128>(), 8);
+    let mut difference = a;
+    difference -= b;
+    assert_eq!(difference.get(&1), 1);
+    assert_eq!(difference.get(&2), 1);
+    assert_eq!(difference.len(), 2);
+}
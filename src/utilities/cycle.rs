@@ -0,0 +1,143 @@
+//! Cycle detection for deterministic simulations.
+//!
+//! Every detector iterates `step` starting at `start` and returns `(mu, lambda)`, where `mu` is the
+//! index of the first state that is part of the cycle and `lambda` is the length of the cycle.
+//! All of them loop forever if the sequence never repeats.
+
+use std::{collections::HashMap, hash::Hash};
+
+/// Detects the cycle with Floyd's tortoise and hare algorithm using constant memory.
+pub fn floyd<S, F>(start: S, step: F) -> (usize, usize)
+where
+    S: PartialEq,
+    F: Fn(&S) -> S,
+{
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&step(&hare));
+    }
+    let mut mu = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+    let mut lambda = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        lambda += 1;
+    }
+    (mu, lambda)
+}
+
+/// Detects the cycle with Brent's algorithm, which needs fewer calls to `step` than [`floyd`].
+pub fn brent<S, F>(start: S, step: F) -> (usize, usize)
+where
+    S: PartialEq + Clone,
+    F: Fn(&S) -> S,
+{
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare);
+        lambda += 1;
+    }
+    let mut mu = 0;
+    tortoise = start.clone();
+    hare = start;
+    for _ in 0..lambda {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+    (mu, lambda)
+}
+
+/// Detects the cycle by remembering every visited state.
+///
+/// Every state is computed exactly once, which makes this the fastest option if `step` is
+/// expensive and the states before the first repetition fit into memory.
+pub fn first_repeat<S, F>(start: S, step: F) -> (usize, usize)
+where
+    S: Hash + Eq,
+    F: Fn(&S) -> S,
+{
+    let mut seen = HashMap::new();
+    let mut state = start;
+    let mut index = 0;
+    loop {
+        if let Some(&first) = seen.get(&state) {
+            return (first, index - first);
+        }
+        let next = step(&state);
+        seen.insert(state, index);
+        state = next;
+        index += 1;
+    }
+}
+
+/// Maps the step count `n` onto the smallest step count that reaches the same state in a sequence
+/// with the cycle `(mu, lambda)`.
+#[inline]
+pub fn reduce_steps(n: u128, (mu, lambda): (usize, usize)) -> usize {
+    if n < mu as u128 {
+        n as usize
+    } else {
+        mu + ((n - mu as u128) % lambda as u128) as usize
+    }
+}
+
+/// Returns the state after applying `step` `n` times, skipping all full cycles.
+pub fn nth_state<S, F>(start: S, step: F, n: u128) -> S
+where
+    S: PartialEq + Clone,
+    F: Fn(&S) -> S,
+{
+    let cycle = brent(start.clone(), &step);
+    let mut state = start;
+    for _ in 0..reduce_steps(n, cycle) {
+        state = step(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+fn tail_then_cycle(x: &u32) -> u32 {
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 6 -> 3
+    if *x < 3 {
+        x + 1
+    } else {
+        3 + (x - 2) % 4
+    }
+}
+#[test]
+fn test_cycle_detectors_agree() {
+    assert_eq!(floyd(0, tail_then_cycle), (3, 4));
+    assert_eq!(brent(0, tail_then_cycle), (3, 4));
+    assert_eq!(first_repeat(0, tail_then_cycle), (3, 4));
+    let pseudo_random = |x: &u64| (x * x + 1) % 65_521;
+    let expected = first_repeat(7, pseudo_random);
+    assert_eq!(floyd(7, pseudo_random), expected);
+    assert_eq!(brent(7, pseudo_random), expected);
+}
+#[test]
+fn test_nth_state() {
+    assert_eq!(nth_state(0, tail_then_cycle, 2), 2);
+    assert_eq!(nth_state(0, tail_then_cycle, 7), 3);
+    assert_eq!(nth_state(0, tail_then_cycle, 1_000_000_000_000_002), 6);
+    assert_eq!(reduce_steps(1_000_000_000_000_002, (3, 4)), 6);
+}
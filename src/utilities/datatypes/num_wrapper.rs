@@ -1,20 +1,193 @@
-use std::ops::{Add, AddAssign, Deref, DerefMut};
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    hash::Hash,
+    iter::{Product, Sum},
+    marker::PhantomData,
+    ops::{
+        Add, AddAssign, Deref, DerefMut, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub,
+        SubAssign,
+    },
+};
 
+/// An arithmetic operation that a [`NumWrapper`] can perform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Operation::Add => "+",
+            Operation::Sub => "-",
+            Operation::Mul => "*",
+            Operation::Div => "/",
+            Operation::Rem => "%",
+        })
+    }
+}
+
+/// The primitive integer operations needed by the [`OverflowPolicy`] implementations.
+pub trait Arithmetic: std::num::ZeroablePrimitive + Copy + Ord + Hash + Debug + Display {
+    const ZERO: Self;
+    const ONE: Self;
+    /// Behaves like the primitive operator, so it panics on overflow in debug builds only.
+    fn native(self, rhs: Self, operation: Operation) -> Self;
+    fn checked(self, rhs: Self, operation: Operation) -> Option<Self>;
+    fn wrapping(self, rhs: Self, operation: Operation) -> Self;
+    fn saturating(self, rhs: Self, operation: Operation) -> Self;
+}
+macro_rules! impl_arithmetic {
+    ($($t:ty),+) => {
+        $(
+        impl Arithmetic for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            #[inline(always)]
+            fn native(self, rhs: Self, operation: Operation) -> Self {
+                match operation {
+                    Operation::Add => self + rhs,
+                    Operation::Sub => self - rhs,
+                    Operation::Mul => self * rhs,
+                    Operation::Div => self / rhs,
+                    Operation::Rem => self % rhs,
+                }
+            }
+            #[inline(always)]
+            fn checked(self, rhs: Self, operation: Operation) -> Option<Self> {
+                match operation {
+                    Operation::Add => self.checked_add(rhs),
+                    Operation::Sub => self.checked_sub(rhs),
+                    Operation::Mul => self.checked_mul(rhs),
+                    Operation::Div => self.checked_div(rhs),
+                    Operation::Rem => self.checked_rem(rhs),
+                }
+            }
+            #[inline(always)]
+            fn wrapping(self, rhs: Self, operation: Operation) -> Self {
+                match operation {
+                    Operation::Add => self.wrapping_add(rhs),
+                    Operation::Sub => self.wrapping_sub(rhs),
+                    Operation::Mul => self.wrapping_mul(rhs),
+                    Operation::Div => self.wrapping_div(rhs),
+                    Operation::Rem => self.wrapping_rem(rhs),
+                }
+            }
+            #[inline(always)]
+            fn saturating(self, rhs: Self, operation: Operation) -> Self {
+                match operation {
+                    Operation::Add => self.saturating_add(rhs),
+                    Operation::Sub => self.saturating_sub(rhs),
+                    Operation::Mul => self.saturating_mul(rhs),
+                    Operation::Div => self.saturating_div(rhs),
+                    // the only overflowing remainder is `MIN % -1`, which saturates to 0 as well
+                    Operation::Rem => self.wrapping_rem(rhs),
+                }
+            }
+        }
+        )+
+    };
+}
+impl_arithmetic!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Decides what a [`NumWrapper`] does when an operation overflows.
+pub trait OverflowPolicy: Copy {
+    fn apply<T: Arithmetic>(lhs: T, rhs: T, operation: Operation) -> T;
+}
+/// Behaves like the primitive operators, which panic on overflow in debug builds only.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Native;
+/// Panics with an [`OverflowError`] on overflow, even in release builds.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Checked;
+/// Wraps around at the boundary of the type.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Wrapping;
+/// Clamps the result to the minimum or maximum of the type.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Saturating;
+
+impl OverflowPolicy for Native {
+    #[inline(always)]
+    fn apply<T: Arithmetic>(lhs: T, rhs: T, operation: Operation) -> T {
+        lhs.native(rhs, operation)
+    }
+}
+impl OverflowPolicy for Checked {
+    #[inline(always)]
+    fn apply<T: Arithmetic>(lhs: T, rhs: T, operation: Operation) -> T {
+        lhs.checked(rhs, operation).unwrap_or_else(|| {
+            panic!(
+                "{}",
+                OverflowError {
+                    lhs,
+                    rhs,
+                    operation
+                }
+            )
+        })
+    }
+}
+impl OverflowPolicy for Wrapping {
+    #[inline(always)]
+    fn apply<T: Arithmetic>(lhs: T, rhs: T, operation: Operation) -> T {
+        lhs.wrapping(rhs, operation)
+    }
+}
+impl OverflowPolicy for Saturating {
+    #[inline(always)]
+    fn apply<T: Arithmetic>(lhs: T, rhs: T, operation: Operation) -> T {
+        lhs.saturating(rhs, operation)
+    }
+}
+
+/// An operation whose result is not representable, either because it overflowed or because it
+/// divided by zero.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OverflowError<T> {
+    pub lhs: T,
+    pub rhs: T,
+    pub operation: Operation,
+}
+impl<T> Display for OverflowError<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "The result of {} {} {} is not representable",
+            self.lhs, self.operation, self.rhs
+        ))
+    }
+}
+impl<T> std::error::Error for OverflowError<T> where T: Debug + Display {}
+
+/// A numeric accumulator whose behaviour on overflow is selected by the policy `P`.
+///
+/// The default policy [`Native`] behaves exactly like the wrapped primitive.
 #[derive(Clone, Copy)]
-pub struct NumWrapper<T>(T)
+pub struct NumWrapper<T, P = Native>(T, PhantomData<P>)
 where
     T: std::num::ZeroablePrimitive;
 
-impl<T> NumWrapper<T>
+impl<T, P> NumWrapper<T, P>
 where
     T: std::num::ZeroablePrimitive,
 {
     #[inline]
     pub fn new(value: T) -> Self {
-        Self(value)
+        Self(value, PhantomData)
+    }
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
     }
 }
-impl<T> Deref for NumWrapper<T>
+impl<T, P> Deref for NumWrapper<T, P>
 where
     T: std::num::ZeroablePrimitive,
 {
@@ -25,7 +198,7 @@ where
         &self.0
     }
 }
-impl<T> DerefMut for NumWrapper<T>
+impl<T, P> DerefMut for NumWrapper<T, P>
 where
     T: std::num::ZeroablePrimitive,
 {
@@ -33,44 +206,210 @@ where
         &mut self.0
     }
 }
+impl<T, P> From<T> for NumWrapper<T, P>
+where
+    T: std::num::ZeroablePrimitive,
+{
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+impl<T, P> Default for NumWrapper<T, P>
+where
+    T: Arithmetic,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::ZERO)
+    }
+}
+
+macro_rules! impl_operation {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $try_method:ident) => {
+        impl<T, P> NumWrapper<T, P>
+        where
+            T: Arithmetic,
+        {
+            /// Performs the operation independent of the policy.
+            ///
+            /// # Returns
+            /// An [`OverflowError`] if the result is not representable.
+            #[inline]
+            pub fn $try_method(self, rhs: T) -> Result<Self, OverflowError<T>> {
+                self.0
+                    .checked(rhs, Operation::$trait)
+                    .map(Self::new)
+                    .ok_or(OverflowError {
+                        lhs: self.0,
+                        rhs,
+                        operation: Operation::$trait,
+                    })
+            }
+        }
+        impl<T, P> $trait<T> for NumWrapper<T, P>
+        where
+            T: Arithmetic,
+            P: OverflowPolicy,
+        {
+            type Output = NumWrapper<T, P>;
 
-impl<T> Add for NumWrapper<T>
+            #[inline]
+            fn $method(self, rhs: T) -> Self::Output {
+                Self::new(P::apply(self.0, rhs, Operation::$trait))
+            }
+        }
+        impl<T, P> $trait for NumWrapper<T, P>
+        where
+            T: Arithmetic,
+            P: OverflowPolicy,
+        {
+            type Output = NumWrapper<T, P>;
+
+            #[inline]
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.$method(rhs.0)
+            }
+        }
+        impl<T, P> $assign_trait<T> for NumWrapper<T, P>
+        where
+            T: Arithmetic,
+            P: OverflowPolicy,
+        {
+            #[inline]
+            fn $assign_method(&mut self, rhs: T) {
+                *self = self.$method(rhs);
+            }
+        }
+        impl<T, P> $assign_trait for NumWrapper<T, P>
+        where
+            T: Arithmetic,
+            P: OverflowPolicy,
+        {
+            #[inline]
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = self.$method(rhs.0);
+            }
+        }
+    };
+}
+impl_operation!(Add, add, AddAssign, add_assign, try_add);
+impl_operation!(Sub, sub, SubAssign, sub_assign, try_sub);
+impl_operation!(Mul, mul, MulAssign, mul_assign, try_mul);
+impl_operation!(Div, div, DivAssign, div_assign, try_div);
+impl_operation!(Rem, rem, RemAssign, rem_assign, try_rem);
+
+impl<T, P> Sum<T> for NumWrapper<T, P>
+where
+    T: Arithmetic,
+    P: OverflowPolicy,
+{
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Self::new(T::ZERO), Add::add)
+    }
+}
+impl<T, P> Sum for NumWrapper<T, P>
+where
+    T: Arithmetic,
+    P: OverflowPolicy,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(T::ZERO), Add::add)
+    }
+}
+impl<T, P> Product<T> for NumWrapper<T, P>
+where
+    T: Arithmetic,
+    P: OverflowPolicy,
+{
+    fn product<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Self::new(T::ONE), Mul::mul)
+    }
+}
+impl<T, P> Product for NumWrapper<T, P>
 where
-    T: std::num::ZeroablePrimitive + Add<Output = T>,
+    T: Arithmetic,
+    P: OverflowPolicy,
 {
-    type Output = NumWrapper<T>;
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(T::ONE), Mul::mul)
+    }
+}
 
+impl<T, P> PartialEq for NumWrapper<T, P>
+where
+    T: std::num::ZeroablePrimitive + PartialEq,
+{
     #[inline]
-    fn add(self, rhs: Self) -> Self::Output {
-        NumWrapper(self.0.add(rhs.0))
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
     }
 }
-impl<T> Add<T> for NumWrapper<T>
+impl<T, P> Eq for NumWrapper<T, P> where T: std::num::ZeroablePrimitive + Eq {}
+impl<T, P> PartialOrd for NumWrapper<T, P>
 where
-    T: std::num::ZeroablePrimitive + Add<Output = T>,
+    T: std::num::ZeroablePrimitive + Ord,
 {
-    type Output = NumWrapper<T>;
-
     #[inline]
-    fn add(self, rhs: T) -> Self::Output {
-        NumWrapper(self.0.add(rhs))
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
-impl<T> AddAssign<T> for NumWrapper<T>
+impl<T, P> Ord for NumWrapper<T, P>
 where
-    T: std::num::ZeroablePrimitive + Add<Output = T> + AddAssign<T>,
+    T: std::num::ZeroablePrimitive + Ord,
 {
     #[inline]
-    fn add_assign(&mut self, rhs: T) {
-        self.0.add_assign(rhs);
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
     }
 }
-impl<T> AddAssign<NumWrapper<T>> for NumWrapper<T>
+impl<T, P> Hash for NumWrapper<T, P>
 where
-    T: std::num::ZeroablePrimitive + Add<Output = T> + AddAssign<T>,
+    T: std::num::ZeroablePrimitive + Hash,
 {
     #[inline]
-    fn add_assign(&mut self, rhs: NumWrapper<T>) {
-        self.0.add_assign(rhs.0);
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
     }
 }
+impl<T, P> Debug for NumWrapper<T, P>
+where
+    T: std::num::ZeroablePrimitive + Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+impl<T, P> Display for NumWrapper<T, P>
+where
+    T: std::num::ZeroablePrimitive + Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[test]
+fn test_num_wrapper_arithmetic() {
+    let value: NumWrapper<i32> = [1, 2, 3, 4].into_iter().sum();
+    assert_eq!(*value, 10);
+    assert_eq!(*((value - 4) * 3 / 4 % 3), 1);
+    let product: NumWrapper<u64> = (1..=5).map(NumWrapper::new).product();
+    assert_eq!(product.to_string(), "120");
+    assert!(NumWrapper::<u8>::new(3) < NumWrapper::new(4));
+}
+#[test]
+fn test_num_wrapper_overflow_policies() {
+    assert_eq!(*(NumWrapper::<u8, Wrapping>::new(250) + 10), 4);
+    assert_eq!(*(NumWrapper::<u8, Saturating>::new(250) + 10), 255);
+    assert_eq!(*(NumWrapper::<i8, Saturating>::new(-100) * 2), -128);
+    let error = NumWrapper::<u8>::new(250).try_add(10).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "The result of 250 + 10 is not representable"
+    );
+    assert!(NumWrapper::<u32, Checked>::new(1).try_div(0).is_err());
+    let overflow = std::panic::catch_unwind(|| NumWrapper::<u8, Checked>::new(16) * 16);
+    assert!(overflow.is_err());
+}
@@ -1,10 +1,11 @@
 use core::num;
 use std::{
-    ops::{Deref, DerefMut},
+    hash::Hash,
+    ops::{Add, Deref, DerefMut, RangeInclusive, Sub},
     str::FromStr,
 };
 
-use crate::utilities::{CollectIntoResult as _, MapParse};
+use crate::utilities::{CollectIntoResult as _, Counter, MapParse};
 
 pub struct NumberList<T>
 where
@@ -20,6 +21,106 @@ where
         Self { list }
     }
 }
+impl<T> NumberList<T>
+where
+    T: num::ZeroablePrimitive + FromStr,
+{
+    /// Parses numbers separated by `delimiter`, ignoring whitespace around every number.
+    pub fn parse_with_delimiter(s: &str, delimiter: &str) -> Result<Self, <T as FromStr>::Err> {
+        s.trim()
+            .split(delimiter)
+            .map(str::trim)
+            .parse()
+            .collect_result()
+            .map(Self::new)
+    }
+    /// Extracts every integer out of mixed text, e.g. `[3, -4]` out of `"p=3 v=-4"`.
+    ///
+    /// A `-` directly in front of digits is treated as a sign, unless `T` can not represent
+    /// negative numbers.
+    pub fn extract(s: &str) -> Result<Self, <T as FromStr>::Err> {
+        let bytes = s.as_bytes();
+        let mut list = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            if !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            let signed = start > 0 && bytes[start - 1] == b'-';
+            let value = match signed {
+                true => s[start - 1..i].parse().or_else(|_| s[start..i].parse()),
+                false => s[start..i].parse(),
+            };
+            list.push(value?);
+        }
+        Ok(Self::new(list))
+    }
+}
+impl<T> NumberList<T>
+where
+    T: num::ZeroablePrimitive + Copy + Ord + Sub<Output = T>,
+{
+    /// Returns the difference of every number to its predecessor.
+    ///
+    /// # Panics
+    /// In debug builds if a difference is not representable, e.g. for a decreasing list of
+    /// unsigned numbers.
+    pub fn differences(&self) -> NumberList<T> {
+        self.list.windows(2).map(|pair| pair[1] - pair[0]).collect()
+    }
+    /// Returns `true` if the numbers never decrease or never increase.
+    pub fn is_monotonic(&self) -> bool {
+        self.list.is_sorted() || self.list.iter().rev().is_sorted()
+    }
+    /// Returns `true` if the numbers are strictly increasing or strictly decreasing and every
+    /// step between two neighbours has a size inside `steps`.
+    ///
+    /// Lists with less than two numbers have no direction and are never accepted.
+    pub fn is_monotonic_with_step(&self, steps: RangeInclusive<T>) -> bool {
+        if self.list.len() < 2 {
+            return false;
+        }
+        let increasing = self.list[0] < self.list[1];
+        self.list.windows(2).all(|pair| match increasing {
+            true => pair[0] < pair[1] && steps.contains(&(pair[1] - pair[0])),
+            false => pair[0] > pair[1] && steps.contains(&(pair[0] - pair[1])),
+        })
+    }
+}
+impl<T> NumberList<T>
+where
+    T: num::ZeroablePrimitive + Copy + Ord,
+{
+    /// Returns a sorted copy of the list.
+    pub fn sorted(&self) -> NumberList<T> {
+        let mut list = self.list.clone();
+        list.sort_unstable();
+        Self::new(list)
+    }
+    /// Returns the element in the middle of the list, which is the later one for an even length.
+    #[inline]
+    pub fn middle(&self) -> Option<T> {
+        self.list.get(self.list.len() / 2).copied()
+    }
+    /// Returns the median, which is the larger of the two middle values for an even length.
+    #[inline]
+    pub fn median(&self) -> Option<T> {
+        self.sorted().middle()
+    }
+    /// Counts how often every number occurs.
+    pub fn frequencies<C>(&self) -> Counter<T, C>
+    where
+        T: Hash,
+        C: num::ZeroablePrimitive + From<u8> + Copy + Ord + Add<Output = C> + Sub<Output = C>,
+    {
+        self.list.iter().copied().collect()
+    }
+}
 impl<T> Deref for NumberList<T>
 where
     T: num::ZeroablePrimitive,
@@ -67,3 +168,36 @@ fn test_number_list() {
     test[1] = 5;
     assert_eq!(test[1], 5);
 }
+#[test]
+fn test_number_list_parsing() {
+    let list = NumberList::<u32>::parse_with_delimiter("75,47, 61,53\n", ",").unwrap();
+    assert_eq!(*list, vec![75, 47, 61, 53]);
+    assert!(NumberList::<u32>::parse_with_delimiter("1,,2", ",").is_err());
+    let list = NumberList::<i32>::extract("p=0,4 v=3,-3 x-1").unwrap();
+    assert_eq!(*list, vec![0, 4, 3, -3, -1]);
+    let list = NumberList::<u8>::extract("Button A: X+94, Y-34").unwrap();
+    assert_eq!(*list, vec![94, 34]);
+    assert!(NumberList::<u8>::extract("value 300").is_err());
+}
+#[test]
+fn test_number_list_sequences() {
+    let list = NumberList::new(vec![7, 6, 4, 2, 1]);
+    assert_eq!(*list.differences(), vec![-1, -2, -2, -1]);
+    assert!(list.is_monotonic());
+    assert!(list.is_monotonic_with_step(1..=2));
+    assert!(!list.is_monotonic_with_step(1..=1));
+    assert!(!NumberList::new(vec![1u32, 3, 3, 4]).is_monotonic_with_step(1..=3));
+    assert!(!NumberList::new(vec![1u32]).is_monotonic_with_step(1..=3));
+    assert!(!NumberList::new(vec![1, 3, 2]).is_monotonic());
+}
+#[test]
+fn test_number_list_statistics() {
+    let list = NumberList::new(vec![3, 4, 2, 1, 3, 3]);
+    assert_eq!(*list.sorted(), vec![1, 2, 3, 3, 3, 4]);
+    assert_eq!(list.middle(), Some(1));
+    assert_eq!(list.median(), Some(3));
+    assert_eq!(NumberList::<u8>::new(vec![]).median(), None);
+    let frequencies: Counter<i32> = list.frequencies();
+    assert_eq!(frequencies.get(&3), 3);
+    assert_eq!(frequencies.get(&5), 0);
+}
@@ -0,0 +1,224 @@
+//! Differential testing of the optimized solutions against naive reference implementations.
+//!
+//! Both are run on many small generated inputs. The first input they disagree on is shrunk to a
+//! minimal input that still shows the disagreement, which is far easier to debug than a random
+//! one.
+
+use std::{
+    fmt::{Debug, Display},
+    ops::Range,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use super::rng::Rng;
+
+/// The amount of generated inputs every check runs on.
+pub const CASES: u64 = 100;
+
+/// How a failing input is made smaller while shrinking.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shrink {
+    /// Removes whole lines and chunks of lines, for inputs that consist of independent lines or a
+    /// single line of tokens.
+    Lines,
+    /// Removes a row together with the column of the same index, keeping a grid square, and
+    /// replaces single cells with `filler`.
+    Grid { filler: char },
+    /// Only replaces single cells with `filler`, keeping the size of the grid.
+    Cells { filler: char },
+}
+impl Shrink {
+    fn candidates(self, input: &str) -> Vec<String> {
+        let lines: Vec<&str> = input.lines().collect();
+        let mut candidates = Vec::new();
+        match self {
+            Shrink::Lines => {
+                for i in 0..lines.len() {
+                    candidates.push(join_lines(lines.iter().take(i).chain(&lines[i + 1..])));
+                }
+                for (i, line) in lines.iter().enumerate() {
+                    let mut chunk = line.len() / 2;
+                    while chunk > 0 {
+                        for start in (0..line.len()).step_by(chunk) {
+                            let end = (start + chunk).min(line.len());
+                            let shorter = [&line[..start], &line[end..]].concat();
+                            let mut lines = lines.clone();
+                            lines[i] = &shorter;
+                            candidates.push(join_lines(lines.iter()));
+                        }
+                        chunk /= 2;
+                    }
+                }
+            }
+            Shrink::Grid { filler } => {
+                for i in 0..lines.len() {
+                    let without_column = |line: &&str| -> String {
+                        let mut line = line.to_string();
+                        if i < line.len() {
+                            line.remove(i);
+                        }
+                        line
+                    };
+                    let rows = lines.iter().take(i).chain(&lines[i + 1..]);
+                    candidates.push(join_lines(rows.map(without_column)));
+                }
+                candidates.extend(Shrink::Cells { filler }.candidates(input));
+            }
+            Shrink::Cells { filler } => {
+                for (row, line) in lines.iter().enumerate() {
+                    for (column, c) in line.char_indices() {
+                        if c == filler {
+                            continue;
+                        }
+                        let mut lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+                        lines[row]
+                            .replace_range(column..column + c.len_utf8(), &filler.to_string());
+                        candidates.push(join_lines(lines.iter()));
+                    }
+                }
+            }
+        }
+        candidates
+    }
+}
+fn join_lines<S: AsRef<str>>(lines: impl Iterator<Item = S>) -> String {
+    lines.fold(String::new(), |mut input, line| {
+        input.push_str(line.as_ref());
+        input.push('\n');
+        input
+    })
+}
+
+/// An input the optimized solution and the reference disagree on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch<T> {
+    /// The seed of the generated input before shrinking.
+    pub seed: u64,
+    /// The shrunk input.
+    pub input: String,
+    pub expected: T,
+    /// The answer of the optimized solution, or its panic message.
+    pub found: Result<T, String>,
+}
+impl<T> Display for Mismatch<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "The solutions disagree on the input shrunk from seed {}:",
+            self.seed
+        )?;
+        writeln!(f, "{}", self.input)?;
+        writeln!(f, "reference: {:?}", self.expected)?;
+        match &self.found {
+            Ok(found) => write!(f, "optimized: {found:?}"),
+            Err(message) => write!(f, "optimized panicked: {message}"),
+        }
+    }
+}
+
+/// Runs `optimized` and `reference` on [`CASES`] inputs of `generate`, cycling through `sizes`.
+///
+/// # Returns
+/// The first disagreement, shrunk with `shrink`. Inputs the reference panics on are never
+/// reported, because shrinking can make an input invalid.
+pub fn check<T, G, A, B>(
+    generate: G,
+    sizes: Range<usize>,
+    shrink: Shrink,
+    optimized: A,
+    reference: B,
+) -> Result<(), Mismatch<T>>
+where
+    T: PartialEq,
+    G: Fn(usize, &mut Rng) -> String,
+    A: Fn(&str) -> T,
+    B: Fn(&str) -> T,
+{
+    let run = |input: &str| -> Option<(T, Result<T, String>)> {
+        let expected = catch_unwind(AssertUnwindSafe(|| reference(input))).ok()?;
+        let found = catch_unwind(AssertUnwindSafe(|| optimized(input))).map_err(panic_message);
+        match found.as_ref() == Ok(&expected) {
+            true => None,
+            false => Some((expected, found)),
+        }
+    };
+    for seed in 0..CASES {
+        let size = sizes.start + seed as usize % sizes.len();
+        let mut input = generate(size, &mut Rng::new(seed));
+        let Some(mut mismatch) = run(&input) else {
+            continue;
+        };
+        'shrink: loop {
+            for candidate in shrink.candidates(&input) {
+                if let Some(smaller) = run(&candidate) {
+                    (input, mismatch) = (candidate, smaller);
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+        let (expected, found) = mismatch;
+        return Err(Mismatch {
+            seed,
+            input,
+            expected,
+            found,
+        });
+    }
+    Ok(())
+}
+/// Like [`check`], but panics with the shrunk input on a disagreement.
+pub fn assert_agree<T, G, A, B>(
+    generate: G,
+    sizes: Range<usize>,
+    shrink: Shrink,
+    optimized: A,
+    reference: B,
+) where
+    T: PartialEq + Debug,
+    G: Fn(usize, &mut Rng) -> String,
+    A: Fn(&str) -> T,
+    B: Fn(&str) -> T,
+{
+    if let Err(mismatch) = check(generate, sizes, shrink, optimized, reference) {
+        panic!("{mismatch}");
+    }
+}
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
+}
+
+#[test]
+fn test_shrinking() {
+    let generate = |size: usize, rng: &mut Rng| -> String {
+        (0..size)
+            .map(|_| format!("{}\n", rng.range(0..100)))
+            .collect()
+    };
+    let sum = |input: &str| -> u32 { input.lines().map(|line| line.parse::<u32>().unwrap()).sum() };
+    let wrong_sum = |input: &str| -> u32 {
+        let numbers = input.lines().map(|line| line.parse::<u32>().unwrap());
+        numbers.filter(|&n| n != 7 && n != 42).sum()
+    };
+    assert_eq!(check(generate, 1..20, Shrink::Lines, sum, sum), Ok(()));
+    let mismatch = check(generate, 20..40, Shrink::Lines, wrong_sum, sum).unwrap_err();
+    assert!(mismatch.input == "7\n" || mismatch.input == "42\n");
+    assert_ne!(mismatch.found, Ok(mismatch.expected));
+}
+#[test]
+fn test_grid_shrinking() {
+    let candidates = Shrink::Grid { filler: '.' }.candidates("#.\n.#\n");
+    assert_eq!(candidates[..2], ["#\n", "#\n"]);
+    assert!(candidates.contains(&"..\n.#\n".to_string()));
+    assert!(candidates.contains(&"#.\n..\n".to_string()));
+    assert_eq!(candidates.len(), 4);
+}
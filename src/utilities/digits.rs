@@ -0,0 +1,319 @@
+//! Digit manipulation for every primitive integer type in any base from 2 to 36.
+//!
+//! Signed integers are handled through their absolute value. Operations that build a new number
+//! keep the sign of `self`, so `-12` concatenated with `3` is `-123`.
+//!
+//! Every operation that can overflow has a `checked_*` variant returning `None` instead, while the
+//! plain variant panics on overflow.
+
+pub trait Digits: Sized + Copy {
+    /// Returns the number of digits, which is `1` for zero.
+    fn digit_count(self, base: u32) -> u32;
+    /// Iterates over the digits starting with the most significant one.
+    ///
+    /// Use `.rev()` to start with the least significant digit instead.
+    fn digits(self, base: u32) -> DigitIter;
+    fn digit_sum(self, base: u32) -> u32;
+    /// Returns the digits starting with the most significant one.
+    #[inline]
+    fn to_digits(self, base: u32) -> Vec<u8> {
+        self.digits(base).collect()
+    }
+    /// Builds a number out of digits starting with the most significant one.
+    ///
+    /// # Returns
+    /// `None` if the number overflows or any digit is not smaller than `base`.
+    fn checked_from_digits(digits: &[u8], base: u32) -> Option<Self>;
+    /// Appends the digits of `other` to the digits of `self`.
+    fn checked_concat(self, other: Self, base: u32) -> Option<Self>;
+    /// Splits the number after the first `position` digits, e.g. `1234` at `1` into `(1, 234)`.
+    ///
+    /// # Returns
+    /// `None` if `position` is larger than the digit count.
+    fn checked_split_at_digit(self, position: u32, base: u32) -> Option<(Self, Self)>;
+    /// Reverses the digits, dropping leading zeros of the result.
+    fn checked_reverse_digits(self, base: u32) -> Option<Self>;
+
+    /// # Panics
+    /// If the number overflows or any digit is not smaller than `base`.
+    #[inline]
+    fn from_digits(digits: &[u8], base: u32) -> Self {
+        Self::checked_from_digits(digits, base).expect("The digits do not form a valid number")
+    }
+    /// # Panics
+    /// If the result overflows.
+    #[inline]
+    fn concat(self, other: Self, base: u32) -> Self {
+        self.checked_concat(other, base)
+            .expect("The concatenation overflowed")
+    }
+    /// # Panics
+    /// If `position` is larger than the digit count.
+    #[inline]
+    fn split_at_digit(self, position: u32, base: u32) -> (Self, Self) {
+        self.checked_split_at_digit(position, base)
+            .expect("The split position is larger than the digit count")
+    }
+    /// # Panics
+    /// If the result overflows.
+    #[inline]
+    fn reverse_digits(self, base: u32) -> Self {
+        self.checked_reverse_digits(base)
+            .expect("The reversed number overflowed")
+    }
+}
+
+#[inline(always)]
+fn check_base(base: u32) -> u128 {
+    assert!((2..=36).contains(&base), "The base {base} is not in 2..=36");
+    base as u128
+}
+
+fn count(magnitude: u128, base: u128) -> u32 {
+    let mut count = 1;
+    let mut rest = magnitude / base;
+    while rest != 0 {
+        count += 1;
+        rest /= base;
+    }
+    count
+}
+
+fn concat(a: u128, b: u128, base: u128) -> Option<u128> {
+    a.checked_mul(base.checked_pow(count(b, base))?)?
+        .checked_add(b)
+}
+
+fn split_at(magnitude: u128, position: u32, base: u128) -> Option<(u128, u128)> {
+    let count = count(magnitude, base);
+    if position > count {
+        return None;
+    }
+    // splitting off every digit of a long `u128` overflows the divisor
+    match base.checked_pow(count - position) {
+        Some(divisor) => Some((magnitude / divisor, magnitude % divisor)),
+        None => Some((0, magnitude)),
+    }
+}
+
+fn from_digits(digits: impl Iterator<Item = u128>, base: u128) -> Option<u128> {
+    let mut result: u128 = 0;
+    for digit in digits {
+        if digit >= base {
+            return None;
+        }
+        result = result.checked_mul(base)?.checked_add(digit)?;
+    }
+    Some(result)
+}
+
+/// A double ended iterator over the digits of a number, see [`Digits::digits`].
+#[derive(Clone, Debug)]
+pub struct DigitIter {
+    magnitude: u128,
+    base: u128,
+    count: u32,
+    front: u32,
+    back: u32,
+}
+impl DigitIter {
+    fn new(magnitude: u128, base: u32) -> Self {
+        let base = check_base(base);
+        let count = count(magnitude, base);
+        Self {
+            magnitude,
+            base,
+            count,
+            front: 0,
+            back: count,
+        }
+    }
+    /// Returns the digit at `index`, where `0` is the most significant digit.
+    #[inline(always)]
+    fn digit(&self, index: u32) -> u8 {
+        let power = self.base.pow(self.count - index - 1);
+        ((self.magnitude / power) % self.base) as u8
+    }
+}
+impl Iterator for DigitIter {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.digit(self.front - 1))
+    }
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.back - self.front) as usize;
+        (remaining, Some(remaining))
+    }
+}
+impl DoubleEndedIterator for DigitIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.digit(self.back))
+    }
+}
+impl ExactSizeIterator for DigitIter {}
+
+/// Conversion between an integer and its absolute value, used to implement [`Digits`].
+pub trait Magnitude: Sized + Copy {
+    fn magnitude(self) -> u128;
+    fn is_negative(self) -> bool;
+    fn with_sign(magnitude: u128, negative: bool) -> Option<Self>;
+}
+macro_rules! impl_magnitude_unsigned {
+    ($($t:ty),+) => {
+        $(
+        impl Magnitude for $t {
+            #[inline(always)]
+            fn magnitude(self) -> u128 {
+                self as u128
+            }
+            #[inline(always)]
+            fn is_negative(self) -> bool {
+                false
+            }
+            #[inline(always)]
+            fn with_sign(magnitude: u128, _negative: bool) -> Option<Self> {
+                Self::try_from(magnitude).ok()
+            }
+        }
+        )+
+    };
+}
+macro_rules! impl_magnitude_signed {
+    ($($t:ty),+) => {
+        $(
+        impl Magnitude for $t {
+            #[inline(always)]
+            fn magnitude(self) -> u128 {
+                self.unsigned_abs() as u128
+            }
+            #[inline(always)]
+            fn is_negative(self) -> bool {
+                self < 0
+            }
+            #[inline(always)]
+            fn with_sign(magnitude: u128, negative: bool) -> Option<Self> {
+                // negated before narrowing, so the magnitude of `MIN` fits
+                let value = if negative {
+                    0i128.checked_sub_unsigned(magnitude)?
+                } else {
+                    i128::try_from(magnitude).ok()?
+                };
+                Self::try_from(value).ok()
+            }
+        }
+        )+
+    };
+}
+impl_magnitude_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_magnitude_signed!(i8, i16, i32, i64, i128, isize);
+
+impl<T> Digits for T
+where
+    T: Magnitude,
+{
+    #[inline]
+    fn digit_count(self, base: u32) -> u32 {
+        count(self.magnitude(), check_base(base))
+    }
+    #[inline]
+    fn digits(self, base: u32) -> DigitIter {
+        DigitIter::new(self.magnitude(), base)
+    }
+    #[inline]
+    fn digit_sum(self, base: u32) -> u32 {
+        self.digits(base).map(u32::from).sum()
+    }
+    fn checked_from_digits(digits: &[u8], base: u32) -> Option<Self> {
+        let magnitude = from_digits(digits.iter().map(|&d| d as u128), check_base(base))?;
+        Self::with_sign(magnitude, false)
+    }
+    fn checked_concat(self, other: Self, base: u32) -> Option<Self> {
+        let magnitude = concat(self.magnitude(), other.magnitude(), check_base(base))?;
+        Self::with_sign(magnitude, self.is_negative())
+    }
+    fn checked_split_at_digit(self, position: u32, base: u32) -> Option<(Self, Self)> {
+        let (high, low) = split_at(self.magnitude(), position, check_base(base))?;
+        let negative = self.is_negative();
+        Some((
+            Self::with_sign(high, negative)?,
+            Self::with_sign(low, negative)?,
+        ))
+    }
+    fn checked_reverse_digits(self, base: u32) -> Option<Self> {
+        let digits = self.digits(base).rev().map(|d| d as u128);
+        let magnitude = from_digits(digits, check_base(base))?;
+        Self::with_sign(magnitude, self.is_negative())
+    }
+}
+
+#[test]
+fn test_digit_count() {
+    assert_eq!(0u32.digit_count(10), 1);
+    assert_eq!(9u8.digit_count(10), 1);
+    assert_eq!(10u64.digit_count(10), 2);
+    assert_eq!(u128::MAX.digit_count(10), 39);
+    assert_eq!((-255i32).digit_count(16), 2);
+    assert_eq!(5u8.digit_count(2), 3);
+}
+#[test]
+fn test_digit_iteration() {
+    assert_eq!(1204u32.digits(10).collect::<Vec<_>>(), vec![1, 2, 0, 4]);
+    assert_eq!(
+        1204u32.digits(10).rev().collect::<Vec<_>>(),
+        vec![4, 0, 2, 1]
+    );
+    let mut digits = 123u16.digits(10);
+    assert_eq!(
+        (digits.next(), digits.next_back(), digits.next()),
+        (Some(1), Some(3), Some(2))
+    );
+    assert_eq!(digits.next(), None);
+    assert_eq!(0xbeefu32.to_digits(16), vec![11, 14, 14, 15]);
+    assert_eq!(u32::from_digits(&[11, 14, 14, 15], 16), 0xbeef);
+    assert_eq!(999i64.digit_sum(10), 27);
+}
+#[test]
+fn test_digit_construction() {
+    assert_eq!(12u128.concat(345, 10), 12345);
+    assert_eq!(10u32.concat(0, 10), 100);
+    assert_eq!((-12i32).concat(3, 10), -123);
+    assert_eq!(25u8.checked_concat(6, 10), None);
+    assert_eq!(253456u64.split_at_digit(3, 10), (253, 456));
+    assert_eq!(1000u64.split_at_digit(2, 10), (10, 0));
+    assert_eq!(12u32.checked_split_at_digit(3, 10), None);
+    assert_eq!(1200u32.reverse_digits(10), 21);
+    assert_eq!(199u8.checked_reverse_digits(10), None);
+    assert_eq!((-120i16).reverse_digits(10), -21);
+    assert_eq!(u8::checked_from_digits(&[2, 5, 6], 10), None);
+    assert_eq!(u8::checked_from_digits(&[1, 10], 10), None);
+}
+#[test]
+fn test_digit_construction_at_min() {
+    assert_eq!((-12i8).checked_concat(8, 10), Some(-128));
+    assert_eq!((-12i8).checked_concat(9, 10), None);
+    assert_eq!(i8::MIN.checked_split_at_digit(2, 10), Some((-12, -8)));
+    assert_eq!(
+        i128::MIN.checked_split_at_digit(39, 10),
+        Some((i128::MIN, 0))
+    );
+    assert_eq!(
+        i128::MIN.checked_split_at_digit(0, 10),
+        Some((0, i128::MIN))
+    );
+    assert_eq!(
+        u128::MAX.checked_split_at_digit(0, 10),
+        Some((0, u128::MAX))
+    );
+}
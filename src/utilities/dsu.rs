@@ -0,0 +1,149 @@
+use super::grid::GridPosition;
+
+/// A union-find structure over the elements `0..len` using path compression and union by size.
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    component_count: usize,
+}
+
+impl DisjointSet {
+    /// Creates `len` elements that are all in their own component.
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+            component_count: len,
+        }
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+    /// Returns the representative of the component containing `element`.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut current = element;
+        while self.parent[current] != root {
+            current = std::mem::replace(&mut self.parent[current], root);
+        }
+        root
+    }
+    /// Merges the components of `a` and `b`.
+    ///
+    /// # Returns
+    /// `true` if both elements were in different components before.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            (a, b) = (b, a);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.component_count -= 1;
+        true
+    }
+    #[inline]
+    pub fn same_component(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+    /// Returns the amount of elements in the component containing `element`.
+    #[inline]
+    pub fn component_size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.size[root]
+    }
+    #[inline]
+    pub fn component_count(&self) -> usize {
+        self.component_count
+    }
+    /// Groups all elements by their component.
+    ///
+    /// Components are ordered by their smallest element and the elements of every component are
+    /// sorted in ascending order.
+    pub fn components(&mut self) -> Vec<Vec<usize>> {
+        let mut index_of_root = vec![usize::MAX; self.len()];
+        let mut components: Vec<Vec<usize>> = Vec::with_capacity(self.component_count);
+        for element in 0..self.len() {
+            let root = self.find(element);
+            if index_of_root[root] == usize::MAX {
+                index_of_root[root] = components.len();
+                components.push(Vec::with_capacity(self.size[root]));
+            }
+            components[index_of_root[root]].push(element);
+        }
+        components
+    }
+}
+
+/// Splits a `rows` x `columns` grid into connected components.
+///
+/// Two orthogonally adjacent cells are connected if `same` returns `true` for their positions.
+///
+/// # Returns
+/// The cells of every component in row major order. The components are ordered by their first cell.
+pub fn grid_components<F>(rows: usize, columns: usize, same: F) -> Vec<Vec<GridPosition>>
+where
+    F: Fn(GridPosition, GridPosition) -> bool,
+{
+    let mut set = DisjointSet::new(rows * columns);
+    for row in 0..rows {
+        for column in 0..columns {
+            let index = row * columns + column;
+            if column + 1 < columns && same((row, column), (row, column + 1)) {
+                set.union(index, index + 1);
+            }
+            if row + 1 < rows && same((row, column), (row + 1, column)) {
+                set.union(index, index + columns);
+            }
+        }
+    }
+    set.components()
+        .into_iter()
+        .map(|component| {
+            component
+                .into_iter()
+                .map(|index| (index / columns, index % columns))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_disjoint_set() {
+    let mut set = DisjointSet::new(6);
+    assert!(set.union(0, 1));
+    assert!(set.union(4, 5));
+    assert!(set.union(1, 5));
+    assert!(!set.union(0, 4));
+    assert!(set.same_component(0, 4));
+    assert!(!set.same_component(0, 2));
+    assert_eq!(set.component_size(5), 4);
+    assert_eq!(set.component_count(), 3);
+    assert_eq!(set.components(), vec![vec![0, 1, 4, 5], vec![2], vec![3]]);
+}
+#[test]
+fn test_grid_components() {
+    let grid = ["AAB", "ABB", "CAB"].map(str::as_bytes);
+    let components = grid_components(3, 3, |a, b| grid[a.0][a.1] == grid[b.0][b.1]);
+    assert_eq!(
+        components,
+        vec![
+            vec![(0, 0), (0, 1), (1, 0)],
+            vec![(0, 2), (1, 1), (1, 2), (2, 2)],
+            vec![(2, 0)],
+            vec![(2, 1)],
+        ]
+    );
+}
@@ -0,0 +1,90 @@
+//! A std-only smoke test for the input parsers.
+//!
+//! Every day has a `pub fn fuzz(input: &str)` that runs its parsers on `input`. The parsers have to
+//! return an error instead of panicking on any input.
+//!
+//! The libFuzzer targets in `fuzz/` explore far more inputs, but need `cargo fuzz` and a sanitizer
+//! capable toolchain. This runs the same entry points on mutations of generated inputs in a
+//! normal test run, which already catches slicing, indexing and unwrapping on malformed input.
+
+use std::{
+    fmt::Write,
+    ops::Range,
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use super::rng::Rng;
+
+/// The amount of mutated inputs every smoke test runs on.
+pub const CASES: u64 = 300;
+/// The characters inserted by [`mutate`]: the separators of the puzzle formats, line endings and
+/// characters that take more than one byte in UTF-8.
+const ALPHABET: &[char] = &[
+    '0', '1', '9', '-', '+', ',', '|', ':', '(', ')', ' ', '\t', '\n', '\r', '#', '.', '^', 'X',
+    'a', '\0', '\u{7f}', 'é', '€', '🎄',
+];
+
+/// Changes `input` in one to eight random places by inserting, replacing, removing or duplicating
+/// characters, or by cutting it off.
+pub fn mutate(input: &str, rng: &mut Rng) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for _ in 0..rng.range(1..9) {
+        let position = rng.range(0..chars.len() + 1);
+        let end = rng.range(position..chars.len() + 1);
+        match rng.range(0..5) {
+            0 => chars.insert(position, *rng.choose(ALPHABET)),
+            1 if position < chars.len() => chars[position] = *rng.choose(ALPHABET),
+            2 => drop(chars.drain(position..end)),
+            3 => chars.truncate(position),
+            _ => {
+                let copy = chars[position..end].to_vec();
+                chars.splice(position..position, copy);
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Runs `fuzz` on [`CASES`] mutations of inputs of `generate`, cycling through `sizes`, and on
+/// as many strings of random characters.
+///
+/// # Panics
+/// With the input that made `fuzz` panic.
+pub fn smoke<G, F>(generate: G, sizes: Range<usize>, fuzz: F)
+where
+    G: Fn(usize, &mut Rng) -> String,
+    F: Fn(&str),
+{
+    let run = |input: &str| {
+        if catch_unwind(AssertUnwindSafe(|| fuzz(input))).is_err() {
+            panic!("The parser panicked on the input {input:?}");
+        }
+    };
+    for seed in 0..CASES {
+        let mut rng = Rng::new(seed);
+        let size = sizes.start + seed as usize % sizes.len();
+        run(&mutate(&generate(size, &mut rng), &mut rng));
+        let random = (0..rng.range(0..64)).fold(String::new(), |mut input, _| {
+            input.write_char(*rng.choose(ALPHABET)).unwrap();
+            input
+        });
+        run(&random);
+    }
+}
+
+#[test]
+fn test_mutate() {
+    let mut rng = Rng::new(7);
+    let mutations: Vec<String> = (0..50).map(|_| mutate("mul(1,2)\n", &mut rng)).collect();
+    assert!(mutations.iter().any(|m| m != "mul(1,2)\n"));
+    assert!(mutations.iter().any(|m| m.len() < 9));
+    assert!(mutations.iter().any(|m| !m.is_ascii()));
+}
+#[test]
+#[should_panic(expected = "The parser panicked on the input")]
+fn test_smoke_reports_panics() {
+    let generate = |size: usize, _: &mut Rng| "1|2\n".repeat(size);
+    smoke(generate, 1..4, |input| {
+        let _ = input.split_once('|').unwrap();
+    });
+}
@@ -0,0 +1,43 @@
+//! Coordinates of rectangular grids.
+
+use super::ArrayVec;
+
+/// A `(row, column)` coordinate inside a grid.
+pub type GridPosition = (usize, usize);
+
+/// Returns the orthogonal neighbours of `position` that lie inside a `rows` x `columns` grid, in the
+/// order up, left, down, right.
+#[inline]
+pub fn orthogonal_neighbours(
+    (row, column): GridPosition,
+    rows: usize,
+    columns: usize,
+) -> ArrayVec<GridPosition, 4> {
+    let mut neighbours = ArrayVec::new();
+    if row > 0 {
+        neighbours.push((row - 1, column));
+    }
+    if column > 0 {
+        neighbours.push((row, column - 1));
+    }
+    if row + 1 < rows {
+        neighbours.push((row + 1, column));
+    }
+    if column + 1 < columns {
+        neighbours.push((row, column + 1));
+    }
+    neighbours
+}
+
+#[test]
+fn test_orthogonal_neighbours() {
+    assert_eq!(
+        orthogonal_neighbours((1, 1), 3, 3).as_slice(),
+        &[(0, 1), (1, 0), (2, 1), (1, 2)]
+    );
+    assert_eq!(
+        orthogonal_neighbours((0, 2), 3, 3).as_slice(),
+        &[(0, 1), (1, 2)]
+    );
+    assert!(orthogonal_neighbours((0, 0), 1, 1).is_empty());
+}
@@ -0,0 +1,113 @@
+//! A fast, deterministic hasher for the small integer keys used by the puzzles.
+//!
+//! [`FxHasher`] follows the hash used inside rustc: every word is mixed in with a rotation, an
+//! xor and a multiplication. It is not resistant against collisions crafted on purpose, which is
+//! irrelevant for puzzle inputs, but a lot faster than the default SipHash.
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::{BuildHasherDefault, Hasher},
+};
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+impl FxHasher {
+    #[inline(always)]
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+impl Hasher for FxHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        for &byte in chunks.remainder() {
+            self.add_to_hash(byte as u64);
+        }
+    }
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(i as u64);
+    }
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(i as u64);
+    }
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(i as u64);
+    }
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.add_to_hash(i as u64);
+        self.add_to_hash((i >> 64) as u64);
+    }
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+pub type FxBuildHasher = BuildHasherDefault<FxHasher>;
+/// A [`HashMap`] using the [`FxHasher`], created with `FastHashMap::default()`.
+pub type FastHashMap<K, V> = HashMap<K, V, FxBuildHasher>;
+/// A [`HashSet`] using the [`FxHasher`], created with `FastHashSet::default()`.
+pub type FastHashSet<T> = HashSet<T, FxBuildHasher>;
+
+#[test]
+fn test_fx_hasher_is_deterministic() {
+    use std::hash::BuildHasher;
+    let build = FxBuildHasher::default();
+    assert_eq!(
+        build.hash_one((3isize, -4isize)),
+        build.hash_one((3isize, -4isize))
+    );
+    assert_ne!(
+        build.hash_one((3isize, -4isize)),
+        build.hash_one((-4isize, 3isize))
+    );
+    assert_ne!(build.hash_one(1u64), build.hash_one(2u64));
+    let mut set: FastHashSet<(usize, usize)> = (0..100).map(|i| (i / 10, i % 10)).collect();
+    assert_eq!(set.len(), 100);
+    assert!(set.remove(&(9, 9)));
+    assert!(!set.contains(&(9, 9)));
+}
+
+#[cfg(test)]
+fn insert_and_lookup_grid<S>(b: &mut test::Bencher)
+where
+    S: std::hash::BuildHasher + Default,
+{
+    b.iter(|| {
+        let mut set: HashSet<(isize, isize), S> = HashSet::default();
+        for row in -64..64 {
+            for column in -64..64 {
+                set.insert((row, column));
+            }
+        }
+        (-128..128).filter(|&i| set.contains(&(i, -i))).count()
+    });
+}
+#[bench]
+fn bench_std_hasher(b: &mut test::Bencher) {
+    insert_and_lookup_grid::<std::hash::RandomState>(b);
+}
+#[bench]
+fn bench_fx_hasher(b: &mut test::Bencher) {
+    insert_and_lookup_grid::<FxBuildHasher>(b);
+}
@@ -0,0 +1,406 @@
+//! Priority queues for shortest-path searches and a [`dijkstra`] implementation that works with
+//! all of them.
+
+use std::{hash::Hash, ops::Add};
+
+use super::FastHashMap;
+
+/// A queue that always hands out the key with the smallest priority first.
+pub trait PriorityQueue<K, P> {
+    /// Queues `key` with `priority`.
+    ///
+    /// Queues that can not update a queued key may hand out the same key multiple times.
+    fn push(&mut self, key: K, priority: P);
+    fn pop_min(&mut self) -> Option<(K, P)>;
+}
+
+/// A binary min-heap that knows the position of every key, so the priority of a queued key can be
+/// lowered instead of queueing a duplicate.
+#[derive(Clone, Debug)]
+pub struct IndexedHeap<K, P> {
+    heap: Vec<(K, P)>,
+    positions: FastHashMap<K, usize>,
+}
+
+impl<K, P> Default for IndexedHeap<K, P> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            heap: Vec::new(),
+            positions: FastHashMap::default(),
+        }
+    }
+}
+
+impl<K, P> IndexedHeap<K, P>
+where
+    K: Hash + Eq + Clone,
+    P: Ord + Copy,
+{
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    #[inline]
+    pub fn contains(&self, key: &K) -> bool {
+        self.positions.contains_key(key)
+    }
+    /// Returns the priority `key` is currently queued with.
+    #[inline]
+    pub fn priority(&self, key: &K) -> Option<P> {
+        self.positions.get(key).map(|&index| self.heap[index].1)
+    }
+    #[inline]
+    pub fn peek_min(&self) -> Option<(&K, P)> {
+        self.heap.first().map(|(key, priority)| (key, *priority))
+    }
+    /// Queues `key`, or lowers its priority if it is already queued with a higher one.
+    ///
+    /// # Returns
+    /// `true` if the key was inserted or its priority was lowered.
+    pub fn push(&mut self, key: K, priority: P) -> bool {
+        if self.contains(&key) {
+            return self.decrease_key(&key, priority);
+        }
+        self.positions.insert(key.clone(), self.heap.len());
+        self.heap.push((key, priority));
+        self.sift_up(self.heap.len() - 1);
+        true
+    }
+    /// Lowers the priority of a queued key.
+    ///
+    /// # Returns
+    /// `false` if the key is not queued or `priority` is not lower than its current priority.
+    pub fn decrease_key(&mut self, key: &K, priority: P) -> bool {
+        let Some(&index) = self.positions.get(key) else {
+            return false;
+        };
+        if priority >= self.heap[index].1 {
+            return false;
+        }
+        self.heap[index].1 = priority;
+        self.sift_up(index);
+        true
+    }
+    pub fn pop_min(&mut self) -> Option<(K, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let last = self.heap.len() - 1;
+        self.swap(0, last);
+        let (key, priority) = self.heap.pop()?;
+        self.positions.remove(&key);
+        if !self.heap.is_empty() {
+            self.sift_down(0);
+        }
+        Some((key, priority))
+    }
+    #[inline]
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        *self.positions.get_mut(&self.heap[a].0).unwrap() = a;
+        *self.positions.get_mut(&self.heap[b].0).unwrap() = b;
+    }
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.heap[parent].1 <= self.heap[index].1 {
+                break;
+            }
+            self.swap(parent, index);
+            index = parent;
+        }
+    }
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let mut smallest = index;
+            for child in [2 * index + 1, 2 * index + 2] {
+                if child < self.heap.len() && self.heap[child].1 < self.heap[smallest].1 {
+                    smallest = child;
+                }
+            }
+            if smallest == index {
+                break;
+            }
+            self.swap(index, smallest);
+            index = smallest;
+        }
+    }
+}
+
+impl<K, P> PriorityQueue<K, P> for IndexedHeap<K, P>
+where
+    K: Hash + Eq + Clone,
+    P: Ord + Copy,
+{
+    #[inline]
+    fn push(&mut self, key: K, priority: P) {
+        IndexedHeap::push(self, key, priority);
+    }
+    #[inline]
+    fn pop_min(&mut self) -> Option<(K, P)> {
+        IndexedHeap::pop_min(self)
+    }
+}
+
+/// A monotone bucket queue for small integer priorities.
+///
+/// Pushing and popping take constant time, as long as no pushed priority is smaller than the last
+/// popped one, which always holds for Dijkstra with non-negative weights. Keys are never updated,
+/// so a key pushed twice is handed out twice.
+#[derive(Clone, Debug)]
+pub struct BucketQueue<K> {
+    buckets: Vec<Vec<K>>,
+    current: usize,
+    len: usize,
+}
+
+impl<K> Default for BucketQueue<K> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            buckets: Vec::new(),
+            current: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<K> BucketQueue<K> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// # Panics
+    /// If `priority` is smaller than the priority of the last popped key.
+    pub fn push(&mut self, key: K, priority: usize) {
+        assert!(
+            priority >= self.current,
+            "The priority {priority} is smaller than the last popped priority {}",
+            self.current
+        );
+        if priority >= self.buckets.len() {
+            self.buckets.resize_with(priority + 1, Vec::new);
+        }
+        self.buckets[priority].push(key);
+        self.len += 1;
+    }
+    pub fn pop_min(&mut self) -> Option<(K, usize)> {
+        if self.is_empty() {
+            return None;
+        }
+        while self.buckets[self.current].is_empty() {
+            self.current += 1;
+        }
+        self.len -= 1;
+        let key = self.buckets[self.current].pop()?;
+        Some((key, self.current))
+    }
+}
+
+impl<K> PriorityQueue<K, usize> for BucketQueue<K> {
+    #[inline]
+    fn push(&mut self, key: K, priority: usize) {
+        BucketQueue::push(self, key, priority);
+    }
+    #[inline]
+    fn pop_min(&mut self) -> Option<(K, usize)> {
+        BucketQueue::pop_min(self)
+    }
+}
+
+/// Calculates the distance from the closest of `starts` to every reachable state.
+///
+/// `neighbours` returns every state reachable from a state together with the non-negative cost of
+/// that step. The `queue` decides the performance characteristics, e.g. an [`IndexedHeap`] for
+/// arbitrary costs or a [`BucketQueue`] for small integer costs.
+pub fn dijkstra<S, C, Q, N, I>(
+    queue: Q,
+    starts: impl IntoIterator<Item = S>,
+    neighbours: N,
+) -> FastHashMap<S, C>
+where
+    S: Hash + Eq + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    Q: PriorityQueue<S, C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut distances = FastHashMap::default();
+    search(queue, starts, neighbours, &mut distances, |_| false);
+    distances
+}
+
+/// Like [`dijkstra`], but stops at the first state that satisfies `is_goal`.
+///
+/// # Returns
+/// The closest goal state and its distance, or `None` if no goal is reachable.
+pub fn dijkstra_to<S, C, Q, N, I, G>(
+    queue: Q,
+    starts: impl IntoIterator<Item = S>,
+    neighbours: N,
+    is_goal: G,
+) -> Option<(S, C)>
+where
+    S: Hash + Eq + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    Q: PriorityQueue<S, C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    search(
+        queue,
+        starts,
+        neighbours,
+        &mut FastHashMap::default(),
+        is_goal,
+    )
+}
+
+fn search<S, C, Q, N, I, G>(
+    mut queue: Q,
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: N,
+    distances: &mut FastHashMap<S, C>,
+    mut is_goal: G,
+) -> Option<(S, C)>
+where
+    S: Hash + Eq + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    Q: PriorityQueue<S, C>,
+    N: FnMut(&S) -> I,
+    I: IntoIterator<Item = (S, C)>,
+    G: FnMut(&S) -> bool,
+{
+    let mut best: FastHashMap<S, C> = FastHashMap::default();
+    for start in starts {
+        best.insert(start.clone(), C::default());
+        queue.push(start, C::default());
+    }
+    while let Some((state, distance)) = queue.pop_min() {
+        // queues without decrease-key can hand out outdated duplicates
+        if distances.contains_key(&state) || best.get(&state).is_some_and(|&d| d < distance) {
+            continue;
+        }
+        distances.insert(state.clone(), distance);
+        if is_goal(&state) {
+            return Some((state, distance));
+        }
+        for (next, cost) in neighbours(&state) {
+            let next_distance = distance + cost;
+            if distances.contains_key(&next) || best.get(&next).is_some_and(|&d| d <= next_distance)
+            {
+                continue;
+            }
+            best.insert(next.clone(), next_distance);
+            queue.push(next, next_distance);
+        }
+    }
+    None
+}
+
+#[test]
+fn test_indexed_heap() {
+    let mut heap = IndexedHeap::new();
+    assert!(heap.push('a', 5));
+    assert!(heap.push('b', 3));
+    assert!(heap.push('c', 8));
+    assert!(!heap.push('c', 9));
+    assert!(heap.decrease_key(&'c', 1));
+    assert!(!heap.decrease_key(&'d', 1));
+    assert_eq!(heap.priority(&'c'), Some(1));
+    assert_eq!(heap.len(), 3);
+    assert_eq!(heap.pop_min(), Some(('c', 1)));
+    assert!(!heap.contains(&'c'));
+    assert_eq!(heap.pop_min(), Some(('b', 3)));
+    assert_eq!(heap.pop_min(), Some(('a', 5)));
+    assert_eq!(heap.pop_min(), None);
+}
+#[test]
+fn test_bucket_queue() {
+    let mut queue = BucketQueue::new();
+    queue.push('a', 4);
+    queue.push('b', 1);
+    queue.push('c', 4);
+    assert_eq!(queue.pop_min(), Some(('b', 1)));
+    queue.push('d', 2);
+    assert_eq!(queue.pop_min(), Some(('d', 2)));
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.pop_min().map(|(_, p)| p), Some(4));
+    assert_eq!(queue.pop_min().map(|(_, p)| p), Some(4));
+    assert!(queue.is_empty());
+}
+#[cfg(test)]
+type MazeState = ((usize, usize), usize);
+#[cfg(test)]
+fn reindeer_maze_neighbours<'a>(
+    grid: &'a [&'a str],
+) -> impl FnMut(&MazeState) -> Vec<(MazeState, usize)> + 'a {
+    // directions: 0 east, 1 south, 2 west, 3 north
+    move |&((row, column), direction)| {
+        let (next_row, next_column) = match direction {
+            0 => (row, column + 1),
+            1 => (row + 1, column),
+            2 => (row, column - 1),
+            _ => (row - 1, column),
+        };
+        let mut result = vec![
+            (((row, column), (direction + 1) % 4), 1000),
+            (((row, column), (direction + 3) % 4), 1000),
+        ];
+        if grid[next_row].as_bytes()[next_column] != b'#' {
+            result.push((((next_row, next_column), direction), 1));
+        }
+        result
+    }
+}
+#[test]
+fn test_dijkstra_queues_agree() {
+    let grid = [
+        "#######", //
+        "#....E#", //
+        "#.##.##", //
+        "#S....#", //
+        "#######",
+    ];
+    let start = ((3, 1), 0);
+    let is_goal = |&(position, _): &MazeState| position == (1, 5);
+    let with_heap = dijkstra_to(
+        IndexedHeap::new(),
+        [start],
+        reindeer_maze_neighbours(&grid),
+        is_goal,
+    );
+    let with_buckets = dijkstra_to(
+        BucketQueue::new(),
+        [start],
+        reindeer_maze_neighbours(&grid),
+        is_goal,
+    );
+    assert_eq!(with_heap.map(|(_, cost)| cost), Some(2006));
+    assert_eq!(with_heap, with_buckets);
+    let distances = dijkstra(IndexedHeap::new(), [start], reindeer_maze_neighbours(&grid));
+    assert_eq!(distances[&((3, 2), 0)], 1);
+    assert_eq!(distances[&((3, 1), 2)], 2000);
+    assert_eq!(
+        distances,
+        dijkstra(BucketQueue::new(), [start], reindeer_maze_neighbours(&grid))
+    );
+}
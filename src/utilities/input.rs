@@ -0,0 +1,299 @@
+//! Helpers to slice puzzle inputs into lines, sections and grids.
+//!
+//! The line based helpers accept CRLF line endings and ignore trailing whitespace, so inputs saved
+//! on any platform behave the same. [`Input::sections`] returns unchanged slices of the input,
+//! which are meant to be read with those helpers in turn.
+
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
+
+use super::datatypes::NumberList;
+
+/// An error together with the line of the input that caused it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseLineError<E> {
+    /// The line number starting at `1`.
+    pub line: usize,
+    pub error: E,
+}
+impl<E> Display for ParseLineError<E>
+where
+    E: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("line {}: {}", self.line, self.error))
+    }
+}
+impl<E> std::error::Error for ParseLineError<E> where E: std::fmt::Debug + Display {}
+
+/// A grid row whose length differs from the length of the first row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RaggedRow {
+    pub expected: usize,
+    pub found: usize,
+}
+impl Display for RaggedRow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "expected {} columns but found {}",
+            self.expected, self.found
+        ))
+    }
+}
+
+/// Why an input, or a part of it, could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// A number that is not a valid integer of the expected type.
+    Int(ParseIntError),
+    /// A character that is not allowed at its position.
+    UnexpectedChar(char),
+    /// A value, separator or section the format requires is missing.
+    Missing(&'static str),
+    /// A grid whose rows and columns differ from the expected ones.
+    Dimensions {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    Ragged(RaggedRow),
+    /// An error in the given line, starting at `1`.
+    Line(usize, Box<ParseError>),
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(error) => Display::fmt(error, f),
+            Self::UnexpectedChar(c) => f.write_fmt(format_args!("unexpected character {c:?}")),
+            Self::Missing(what) => f.write_fmt(format_args!("missing {what}")),
+            Self::Dimensions { expected, found } => f.write_fmt(format_args!(
+                "expected {}x{} cells but found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            )),
+            Self::Ragged(error) => Display::fmt(error, f),
+            Self::Line(line, error) => f.write_fmt(format_args!("line {line}: {error}")),
+        }
+    }
+}
+impl std::error::Error for ParseError {}
+impl From<ParseIntError> for ParseError {
+    fn from(value: ParseIntError) -> Self {
+        Self::Int(value)
+    }
+}
+impl From<RaggedRow> for ParseError {
+    fn from(value: RaggedRow) -> Self {
+        Self::Ragged(value)
+    }
+}
+impl<E> From<ParseLineError<E>> for ParseError
+where
+    E: Into<ParseError>,
+{
+    fn from(value: ParseLineError<E>) -> Self {
+        Self::Line(value.line, Box::new(value.error.into()))
+    }
+}
+
+pub type TrimmedLines<'a> = std::iter::Map<std::str::Lines<'a>, fn(&'a str) -> &'a str>;
+
+pub trait Input {
+    /// Iterates over the lines without leading and trailing blank lines and without trailing
+    /// whitespace on every line.
+    fn trimmed_lines(&self) -> TrimmedLines<'_>;
+    /// Like [`Input::trimmed_lines`], but every line is paired with its line number in the
+    /// untrimmed input, starting at `1`.
+    fn numbered_lines(&self) -> impl Iterator<Item = (usize, &str)>;
+    /// Splits the input on blank lines, e.g. into the rules and updates of day 5.
+    ///
+    /// The sections are slices of the input without trailing whitespace, but keep the line endings
+    /// and trailing whitespace of the lines inside them.
+    fn sections(&self) -> Sections<'_>;
+    /// Returns the input with `\n` line endings and without any trailing whitespace.
+    fn normalized(&self) -> String;
+    /// Extracts every integer, including its sign, see [`NumberList::extract`].
+    fn ints<T>(&self) -> Result<NumberList<T>, <T as FromStr>::Err>
+    where
+        T: std::num::ZeroablePrimitive + FromStr;
+    /// Parses every line with `f`.
+    ///
+    /// # Returns
+    /// The first error together with the line it occurred in.
+    fn parse_lines_with<T, E, F>(&self, f: F) -> Result<Vec<T>, ParseLineError<E>>
+    where
+        F: FnMut(&str) -> Result<T, E>;
+    /// Parses every line with [`FromStr`].
+    #[inline]
+    fn parse_lines<T>(&self) -> Result<Vec<T>, ParseLineError<<T as FromStr>::Err>>
+    where
+        T: FromStr,
+    {
+        self.parse_lines_with(str::parse)
+    }
+    /// Splits the input into the characters of every line.
+    ///
+    /// # Returns
+    /// A [`RaggedRow`] error if the lines do not all have the same length.
+    fn grid(&self) -> Result<Vec<Vec<char>>, ParseLineError<RaggedRow>>;
+}
+
+impl Input for str {
+    #[inline]
+    fn trimmed_lines(&self) -> TrimmedLines<'_> {
+        self.trim().lines().map(str::trim_end)
+    }
+    fn numbered_lines(&self) -> impl Iterator<Item = (usize, &str)> {
+        let leading_lines = self[..self.len() - self.trim_start().len()]
+            .matches('\n')
+            .count();
+        self.trimmed_lines()
+            .enumerate()
+            .map(move |(i, line)| (leading_lines + i + 1, line))
+    }
+    #[inline]
+    fn sections(&self) -> Sections<'_> {
+        Sections { rest: self }
+    }
+    fn normalized(&self) -> String {
+        let mut result = String::with_capacity(self.len());
+        for line in self.trim_end().lines() {
+            result.push_str(line.trim_end());
+            result.push('\n');
+        }
+        result.pop();
+        result
+    }
+    #[inline]
+    fn ints<T>(&self) -> Result<NumberList<T>, <T as FromStr>::Err>
+    where
+        T: std::num::ZeroablePrimitive + FromStr,
+    {
+        NumberList::extract(self)
+    }
+    fn parse_lines_with<T, E, F>(&self, mut f: F) -> Result<Vec<T>, ParseLineError<E>>
+    where
+        F: FnMut(&str) -> Result<T, E>,
+    {
+        self.numbered_lines()
+            .map(|(line, s)| f(s).map_err(|error| ParseLineError { line, error }))
+            .collect()
+    }
+    fn grid(&self) -> Result<Vec<Vec<char>>, ParseLineError<RaggedRow>> {
+        let mut grid: Vec<Vec<char>> = Vec::new();
+        for (line, s) in self.numbered_lines() {
+            let row: Vec<char> = s.trim_start().chars().collect();
+            if let Some(first) = grid.first() {
+                if first.len() != row.len() {
+                    let error = RaggedRow {
+                        expected: first.len(),
+                        found: row.len(),
+                    };
+                    return Err(ParseLineError { line, error });
+                }
+            }
+            grid.push(row);
+        }
+        Ok(grid)
+    }
+}
+
+/// An iterator over the blocks of an input that are separated by blank lines, see
+/// [`Input::sections`].
+pub struct Sections<'a> {
+    rest: &'a str,
+}
+impl<'a> Iterator for Sections<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut start = None;
+        let mut offset = 0;
+        for line in self.rest.split_inclusive('\n') {
+            let blank = line.trim().is_empty();
+            match start {
+                None if !blank => start = Some(offset),
+                Some(_) if blank => break,
+                _ => {}
+            }
+            offset += line.len();
+        }
+        let section = &self.rest[start?..offset];
+        self.rest = &self.rest[offset..];
+        Some(section.trim_end())
+    }
+}
+
+/// Returns the amount of rows and columns of a grid, ignoring whitespace and blank lines.
+///
+/// The columns are taken from the first row. Being a `const fn`, this can size the arrays of a
+/// grid that is included with `include_str!`.
+pub const fn grid_dimensions(input: &str) -> (usize, usize) {
+    let bytes = input.as_bytes();
+    let (mut rows, mut columns, mut current) = (0, 0, 0);
+    let mut i = 0;
+    while i <= bytes.len() {
+        if i == bytes.len() || bytes[i] == b'\n' {
+            if current > 0 {
+                if rows == 0 {
+                    columns = current;
+                }
+                rows += 1;
+            }
+            current = 0;
+        } else if !bytes[i].is_ascii_whitespace() {
+            current += 1;
+        }
+        i += 1;
+    }
+    (rows, columns)
+}
+
+#[test]
+fn test_lines_and_sections() {
+    let input = "\r\n47|53  \r\n97|13\r\n\r\n  \r\n75,47\r\n61,53\n\n";
+    assert_eq!(
+        input.trimmed_lines().collect::<Vec<_>>(),
+        vec!["47|53", "97|13", "", "", "75,47", "61,53"]
+    );
+    assert_eq!(
+        input.sections().collect::<Vec<_>>(),
+        vec!["47|53  \r\n97|13", "75,47\r\n61,53"]
+    );
+    assert_eq!(input.normalized(), "\n47|53\n97|13\n\n\n75,47\n61,53");
+    assert_eq!("".sections().next(), None);
+}
+#[test]
+fn test_parse_lines() {
+    let input = "\n1\n2\r\n3\n";
+    assert_eq!(input.parse_lines::<u8>(), Ok(vec![1, 2, 3]));
+    let error = "\n1\nx\n3".parse_lines::<u8>().unwrap_err();
+    assert_eq!(error.line, 3);
+    assert_eq!(error.to_string(), "line 3: invalid digit found in string");
+    assert_eq!(*"p=0,4 v=3,-3".ints::<i32>().unwrap(), vec![0, 4, 3, -3]);
+}
+#[test]
+fn test_parse_error() {
+    let error: ParseError = "1\n-2".parse_lines::<u8>().unwrap_err().into();
+    assert_eq!(error.to_string(), "line 2: invalid digit found in string");
+    let error: ParseError = "##\n#".grid().unwrap_err().into();
+    assert_eq!(error.to_string(), "line 2: expected 2 columns but found 1");
+    let error = ParseError::Dimensions {
+        expected: (140, 140),
+        found: (3, 140),
+    };
+    assert_eq!(error.to_string(), "expected 140x140 cells but found 3x140");
+}
+#[test]
+fn test_grid() {
+    let input = "#.\r\n.#\r\n";
+    assert_eq!(input.grid(), Ok(vec![vec!['#', '.'], vec!['.', '#']]));
+    assert_eq!(grid_dimensions(input), (2, 2));
+    assert_eq!(grid_dimensions("abc\ndef\n\n"), (2, 3));
+    let error = "##\n#\n##".grid().unwrap_err();
+    assert_eq!(error.line, 2);
+    assert_eq!(
+        error.error,
+        RaggedRow {
+            expected: 2,
+            found: 1
+        }
+    );
+}
@@ -0,0 +1,178 @@
+use std::ops::{Add, Range, Sub};
+
+/// A set of integers stored as sorted, disjoint, half-open runs.
+///
+/// Inserting a range merges it with every run it overlaps or touches, so `0..2` and `2..4` are
+/// stored as the single run `0..4`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    runs: Vec<Range<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    #[inline]
+    fn default() -> Self {
+        Self { runs: Vec::new() }
+    }
+}
+
+impl<T> IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds every value of `range` to the set.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.runs.partition_point(|run| run.end < range.start);
+        let last = self.runs.partition_point(|run| run.start <= range.end);
+        let merged = if first < last {
+            T::min(self.runs[first].start, range.start)..T::max(self.runs[last - 1].end, range.end)
+        } else {
+            range
+        };
+        self.runs.splice(first..last, [merged]);
+    }
+    #[inline]
+    pub fn insert_point(&mut self, point: T) {
+        self.insert(point..point + 1u8.into());
+    }
+    /// Removes every value of `range` from the set, splitting runs where necessary.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.runs.partition_point(|run| run.end <= range.start);
+        let last = self.runs.partition_point(|run| run.start < range.end);
+        if first >= last {
+            return;
+        }
+        let left = self.runs[first].start..range.start;
+        let right = range.end..self.runs[last - 1].end;
+        let remaining = [left, right].into_iter().filter(|run| !run.is_empty());
+        self.runs.splice(first..last, remaining);
+    }
+    #[inline]
+    pub fn remove_point(&mut self, point: T) {
+        self.remove(point..point + 1u8.into());
+    }
+    #[inline]
+    pub fn contains(&self, point: T) -> bool {
+        let index = self.runs.partition_point(|run| run.end <= point);
+        self.runs.get(index).is_some_and(|run| run.start <= point)
+    }
+    /// Returns `true` if any value of `range` is in the set.
+    #[inline]
+    pub fn overlaps(&self, range: Range<T>) -> bool {
+        self.overlapping(range).next().is_some()
+    }
+    /// Iterates over every run that shares at least one value with `range`.
+    pub fn overlapping(&self, range: Range<T>) -> std::slice::Iter<'_, Range<T>> {
+        if range.is_empty() {
+            return [].iter();
+        }
+        let first = self.runs.partition_point(|run| run.end <= range.start);
+        let last = self.runs.partition_point(|run| run.start < range.end);
+        self.runs[first..last.max(first)].iter()
+    }
+    /// Iterates over the runs in ascending order.
+    #[inline]
+    pub fn runs(&self) -> std::slice::Iter<'_, Range<T>> {
+        self.runs.iter()
+    }
+    #[inline]
+    pub fn run_count(&self) -> usize {
+        self.runs.len()
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+    /// Returns the amount of values contained in the set.
+    #[inline]
+    pub fn coverage(&self) -> T {
+        self.runs
+            .iter()
+            .fold(0u8.into(), |sum, run| sum + (run.end - run.start))
+    }
+    /// Finds the first gap inside of `within` that is not covered by the set and is at least
+    /// `length` values long.
+    ///
+    /// # Returns
+    /// The whole gap, clipped to `within`, or `None` if no gap is large enough.
+    pub fn first_gap(&self, length: T, within: Range<T>) -> Option<Range<T>> {
+        let mut cursor = within.start;
+        let first = self.runs.partition_point(|run| run.end <= within.start);
+        for run in &self.runs[first..] {
+            let gap_end = T::min(run.start, within.end);
+            if gap_end > cursor && gap_end - cursor >= length {
+                return Some(cursor..gap_end);
+            }
+            if run.start >= within.end {
+                return None;
+            }
+            cursor = T::max(cursor, run.end);
+        }
+        (within.end > cursor && within.end - cursor >= length).then_some(cursor..within.end)
+    }
+}
+
+impl<T> FromIterator<Range<T>> for IntervalSet<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T> + From<u8>,
+{
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[test]
+fn test_interval_set_insert_merges() {
+    let mut set = IntervalSet::new();
+    set.insert(5..7);
+    set.insert(0..2);
+    set.insert(2..3);
+    set.insert_point(8);
+    assert_eq!(
+        set.runs().cloned().collect::<Vec<_>>(),
+        vec![0..3, 5..7, 8..9]
+    );
+    set.insert(4..8);
+    assert_eq!(set.runs().cloned().collect::<Vec<_>>(), vec![0..3, 4..9]);
+    assert_eq!(set.coverage(), 8u32);
+    assert!(set.contains(4));
+    assert!(!set.contains(3));
+    assert!(!set.contains(9));
+}
+#[test]
+fn test_interval_set_remove_splits() {
+    let mut set: IntervalSet<i32> = [0..10, 20..30].into_iter().collect();
+    set.remove(3..5);
+    set.remove(8..22);
+    set.remove_point(29);
+    assert_eq!(
+        set.runs().cloned().collect::<Vec<_>>(),
+        vec![0..3, 5..8, 22..29]
+    );
+    assert!(set.overlaps(7..21));
+    assert!(!set.overlaps(8..22));
+    assert_eq!(set.overlapping(1..6).count(), 2);
+}
+#[test]
+fn test_interval_set_first_gap() {
+    let set: IntervalSet<u32> = [0..2, 5..6, 9..12].into_iter().collect();
+    assert_eq!(set.first_gap(1, 0..20), Some(2..5));
+    assert_eq!(set.first_gap(3, 0..20), Some(2..5));
+    assert_eq!(set.first_gap(4, 0..20), Some(12..20));
+    assert_eq!(set.first_gap(4, 0..9), None);
+    assert_eq!(set.first_gap(2, 3..9), Some(3..5));
+}
@@ -0,0 +1,78 @@
+use super::rational::{Rational, SignedInteger};
+
+/// Solves the linear system `matrix * x = rhs` exactly using Gaussian elimination.
+///
+/// # Parameters
+/// - `matrix`: The coefficients, where `matrix[row][column]` belongs to the variable `x[column]`.
+/// - `rhs`: The right hand side of every equation.
+///
+/// # Returns
+/// - `Some(x)`: The unique solution of the system.
+/// - `None`: If the matrix is singular, so the system has either no or infinitely many solutions.
+pub fn solve_linear_system<T, const N: usize>(
+    mut matrix: [[Rational<T>; N]; N],
+    mut rhs: [Rational<T>; N],
+) -> Option<[Rational<T>; N]>
+where
+    T: SignedInteger,
+{
+    for column in 0..N {
+        let pivot = (column..N).find(|&row| !matrix[row][column].is_zero())?;
+        matrix.swap(column, pivot);
+        rhs.swap(column, pivot);
+        let pivot_row = matrix[column];
+        let pivot_rhs = rhs[column];
+        for row in 0..N {
+            if row == column || matrix[row][column].is_zero() {
+                continue;
+            }
+            let factor = matrix[row][column] / pivot_row[column];
+            for (value, pivot_value) in matrix[row].iter_mut().zip(pivot_row).skip(column) {
+                *value -= pivot_value * factor;
+            }
+            rhs[row] -= pivot_rhs * factor;
+        }
+    }
+    for row in 0..N {
+        rhs[row] /= matrix[row][row];
+    }
+    Some(rhs)
+}
+
+/// Convenience wrapper around [`solve_linear_system`] for integer coefficients.
+#[inline]
+pub fn solve_integer_linear_system<T, const N: usize>(
+    matrix: [[T; N]; N],
+    rhs: [T; N],
+) -> Option<[Rational<T>; N]>
+where
+    T: SignedInteger,
+{
+    solve_linear_system(
+        matrix.map(|row| row.map(Rational::from)),
+        rhs.map(Rational::from),
+    )
+}
+
+#[test]
+fn test_solve_claw_machine() {
+    // Button A: X+94, Y+34; Button B: X+22, Y+67; Prize: X=8400, Y=5400
+    let [a, b] = solve_integer_linear_system([[94i64, 22], [34, 67]], [8400, 5400]).unwrap();
+    assert_eq!(a.to_integer(), Some(80));
+    assert_eq!(b.to_integer(), Some(40));
+    let [a, b] = solve_integer_linear_system([[26i64, 67], [66, 21]], [12748, 12176]).unwrap();
+    assert!(!a.is_integer() || !b.is_integer());
+}
+#[test]
+fn test_solve_needs_pivoting() {
+    let solution =
+        solve_integer_linear_system([[0i64, 2, 1], [1, 1, 1], [2, 0, 3]], [7, 6, 11]).unwrap();
+    assert_eq!(solution, [1, 2, 3].map(Rational::from_integer));
+}
+#[test]
+fn test_solve_singular() {
+    assert_eq!(
+        solve_integer_linear_system([[1i64, 2], [2, 4]], [3, 6]),
+        None
+    );
+}
@@ -0,0 +1,185 @@
+//! The cache behind the [`memoize!`](crate::memoize) macro.
+
+use std::{cell::RefCell, hash::Hash, thread::LocalKey};
+
+use super::FastHashMap;
+
+/// How often a [`MemoCache`] could answer a lookup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+/// The results of a memoized function keyed by its arguments.
+///
+/// The cache also tracks the recursion depth of the function, so a per-call cache can forget its
+/// results once the outermost call returns.
+#[derive(Clone, Debug)]
+pub struct MemoCache<K, V> {
+    values: FastHashMap<K, V>,
+    depth: usize,
+    #[cfg(feature = "debug")]
+    stats: CacheStats,
+}
+
+impl<K, V> Default for MemoCache<K, V> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            values: FastHashMap::default(),
+            depth: 0,
+            #[cfg(feature = "debug")]
+            stats: CacheStats::default(),
+        }
+    }
+}
+
+impl<K, V> MemoCache<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+    #[inline]
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let value = self.values.get(key).cloned();
+        #[cfg(feature = "debug")]
+        match value {
+            Some(_) => self.stats.hits += 1,
+            None => self.stats.misses += 1,
+        }
+        value
+    }
+    #[inline]
+    pub fn insert(&mut self, key: K, value: V) {
+        self.values.insert(key, value);
+    }
+    /// Forgets every result, but keeps the statistics.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+    /// Marks the start of a call of the memoized function.
+    #[inline]
+    pub fn enter(&mut self) {
+        self.depth += 1;
+    }
+    /// Marks the end of a call of the memoized function and clears the cache after the outermost
+    /// call if `per_call` is set.
+    #[inline]
+    pub fn exit(&mut self, per_call: bool) {
+        self.depth -= 1;
+        if per_call && self.depth == 0 {
+            self.clear();
+        }
+    }
+    #[cfg(feature = "debug")]
+    #[inline]
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+/// A call of a memoized function, which is exited when the guard is dropped.
+///
+/// Exiting on drop keeps the recursion depth right when the function panics, otherwise a per-call
+/// cache would never be cleared again.
+pub struct CallGuard<K, V>
+where
+    K: Hash + Eq + 'static,
+    V: Clone + 'static,
+{
+    cache: &'static LocalKey<RefCell<MemoCache<K, V>>>,
+    per_call: bool,
+}
+impl<K, V> CallGuard<K, V>
+where
+    K: Hash + Eq + 'static,
+    V: Clone + 'static,
+{
+    #[inline]
+    pub fn enter(cache: &'static LocalKey<RefCell<MemoCache<K, V>>>, per_call: bool) -> Self {
+        cache.with_borrow_mut(MemoCache::enter);
+        Self { cache, per_call }
+    }
+}
+impl<K, V> Drop for CallGuard<K, V>
+where
+    K: Hash + Eq + 'static,
+    V: Clone + 'static,
+{
+    #[inline]
+    fn drop(&mut self) {
+        self.cache
+            .with_borrow_mut(|cache| cache.exit(self.per_call));
+    }
+}
+
+#[cfg(test)]
+crate::memoize!(global fn fibonacci(n: u32) -> u128 {
+    if n < 2 {
+        return n as u128;
+    }
+    fibonacci(n - 1) + fibonacci(n - 2)
+});
+#[cfg(test)]
+crate::memoize!(per_call fn arrangements(pattern: String, towels: Vec<&'static str>) -> u64 {
+    if pattern.is_empty() {
+        return 1;
+    }
+    towels
+        .iter()
+        .filter_map(|towel| pattern.strip_prefix(towel))
+        .map(|rest| arrangements(rest.to_string(), towels.clone()))
+        .sum()
+});
+#[test]
+fn test_memoize_global() {
+    fibonacci_cache().with_borrow_mut(MemoCache::clear);
+    assert_eq!(fibonacci(150), 9969216677189303386214405760200);
+    assert_eq!(fibonacci_cache().with_borrow(MemoCache::len), 151);
+    fibonacci_cache().with_borrow_mut(MemoCache::clear);
+    assert!(fibonacci_cache().with_borrow(MemoCache::is_empty));
+}
+#[test]
+fn test_memoize_per_call() {
+    let towels = vec!["r", "wr", "b", "g", "bwu", "rb", "gb", "br"];
+    assert_eq!(arrangements("gbbr".to_string(), towels.clone()), 4);
+    assert_eq!(arrangements("rrbgbr".to_string(), towels.clone()), 6);
+    assert!(arrangements_cache().with_borrow(MemoCache::is_empty));
+}
+#[cfg(test)]
+crate::memoize!(per_call fn fragile(n: u32) -> u32 {
+    let result = if n < 2 { n } else { fragile(n - 1) + 1 };
+    assert_ne!(n, 3, "fragile panics at 3");
+    result
+});
+#[test]
+fn test_memoize_per_call_panic() {
+    assert!(std::panic::catch_unwind(|| fragile(5)).is_err());
+    assert!(fragile_cache().with_borrow(MemoCache::is_empty));
+    assert_eq!(fragile(2), 2);
+    assert!(fragile_cache().with_borrow(MemoCache::is_empty));
+}
+#[cfg(feature = "debug")]
+#[test]
+fn test_memoize_stats() {
+    fibonacci_cache().with_borrow_mut(MemoCache::clear);
+    let before = fibonacci_cache().with_borrow(MemoCache::stats);
+    fibonacci(10);
+    let after = fibonacci_cache().with_borrow(MemoCache::stats);
+    assert_eq!(after.misses - before.misses, 11);
+    assert_eq!(after.hits - before.hits, 8);
+}
@@ -0,0 +1,220 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
+    hash::Hash,
+};
+
+/// A cycle found in a [`Relation`], listed in the order of the rules that form it.
+///
+/// The first element is required to come before the second and so on, while the last element is
+/// required to come before the first one again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<T>(pub Vec<T>);
+
+impl<T> Display for Cycle<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for element in &self.0 {
+            f.write_fmt(format_args!("{element} -> "))?;
+        }
+        match self.0.first() {
+            Some(first) => f.write_fmt(format_args!("{first}")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A set of `a before b` rules.
+///
+/// The rules do not have to be complete or even consistent. Contradictions only surface as a
+/// [`Cycle`] once they are relevant for the elements that are ordered.
+#[derive(Clone, Debug)]
+pub struct Relation<T> {
+    successors: HashMap<T, HashSet<T>>,
+}
+
+impl<T> Default for Relation<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            successors: HashMap::new(),
+        }
+    }
+}
+
+impl<T> Relation<T>
+where
+    T: Copy + Hash + Eq,
+{
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds the rule that `before` has to come before `after`.
+    #[inline]
+    pub fn insert(&mut self, before: T, after: T) {
+        self.successors.entry(before).or_default().insert(after);
+    }
+    /// Returns `true` if there is a direct rule that `before` has to come before `after`.
+    #[inline]
+    pub fn contains(&self, before: T, after: T) -> bool {
+        self.successors
+            .get(&before)
+            .is_some_and(|successors| successors.contains(&after))
+    }
+    /// Iterates over every element that directly has to come after `element`.
+    #[inline]
+    pub fn successors(&self, element: T) -> impl Iterator<Item = T> + '_ {
+        self.successors.get(&element).into_iter().flatten().copied()
+    }
+    /// Returns `true` if no element of `sequence` is required to come before an earlier element.
+    pub fn respects(&self, sequence: &[T]) -> bool {
+        sequence.iter().enumerate().all(|(i, &earlier)| {
+            sequence[i + 1..]
+                .iter()
+                .all(|&later| !self.contains(later, earlier))
+        })
+    }
+    /// Compares two elements by their direct rules, treating unrelated elements as equal.
+    ///
+    /// This is only a total order, as required by `sort_by`, if the rules are transitive and every
+    /// pair of sorted elements is related. Use [`Relation::topological_sort`] otherwise.
+    #[inline]
+    pub fn comparator(&self) -> impl Fn(&T, &T) -> Ordering + '_ {
+        |&a, &b| {
+            if self.contains(a, b) {
+                Ordering::Less
+            } else if self.contains(b, a) {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        }
+    }
+    /// Orders the elements of `subset` so that every rule between them is respected, ignoring all
+    /// rules involving elements outside of the subset.
+    ///
+    /// Uses Kahn's algorithm and keeps unrelated elements in their original order. Repeated
+    /// elements are only sorted once.
+    ///
+    /// # Returns
+    /// - `Ok(Vec<T>)`: The sorted elements.
+    /// - `Err(Cycle<T>)`: A cycle between elements of the subset that makes sorting impossible.
+    pub fn topological_sort(&self, subset: &[T]) -> Result<Vec<T>, Cycle<T>> {
+        let members: HashSet<T> = subset.iter().copied().collect();
+        let mut in_degree: HashMap<T, usize> = members.iter().map(|&e| (e, 0)).collect();
+        for &element in &members {
+            for successor in self.successors(element).filter(|s| members.contains(s)) {
+                *in_degree.get_mut(&successor).unwrap() += 1;
+            }
+        }
+        let mut queued = HashSet::new();
+        let mut queue: VecDeque<T> = subset
+            .iter()
+            .copied()
+            .filter(|&element| in_degree[&element] == 0 && queued.insert(element))
+            .collect();
+        let mut sorted = Vec::with_capacity(members.len());
+        while let Some(element) = queue.pop_front() {
+            sorted.push(element);
+            for successor in self.successors(element).filter(|s| members.contains(s)) {
+                let degree = in_degree.get_mut(&successor).unwrap();
+                *degree -= 1;
+                if *degree == 0 && queued.insert(successor) {
+                    queue.push_back(successor);
+                }
+            }
+        }
+        if sorted.len() == members.len() {
+            Ok(sorted)
+        } else {
+            Err(self
+                .find_cycle(subset)
+                .expect("Kahn's algorithm only stops early on a cycle"))
+        }
+    }
+    /// Searches for a cycle between the elements of `subset` using a depth first search.
+    pub fn find_cycle(&self, subset: &[T]) -> Option<Cycle<T>> {
+        let members: HashSet<T> = subset.iter().copied().collect();
+        let mut finished = HashSet::new();
+        let mut path = Vec::new();
+        subset
+            .iter()
+            .find_map(|&start| self.find_cycle_from(start, &members, &mut finished, &mut path))
+    }
+    fn find_cycle_from(
+        &self,
+        element: T,
+        members: &HashSet<T>,
+        finished: &mut HashSet<T>,
+        path: &mut Vec<T>,
+    ) -> Option<Cycle<T>> {
+        if finished.contains(&element) {
+            return None;
+        }
+        if let Some(position) = path.iter().position(|&e| e == element) {
+            return Some(Cycle(path[position..].to_vec()));
+        }
+        path.push(element);
+        for successor in self.successors(element).filter(|s| members.contains(s)) {
+            if let Some(cycle) = self.find_cycle_from(successor, members, finished, path) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        finished.insert(element);
+        None
+    }
+}
+
+impl<T> FromIterator<(T, T)> for Relation<T>
+where
+    T: Copy + Hash + Eq,
+{
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut relation = Self::new();
+        for (before, after) in iter {
+            relation.insert(before, after);
+        }
+        relation
+    }
+}
+
+#[test]
+fn test_topological_sort_subset() {
+    // the full relation is cyclic, but the subset without 4 is not
+    let relation: Relation<u32> = [(1, 2), (2, 3), (3, 4), (4, 1), (1, 3)]
+        .into_iter()
+        .collect();
+    assert_eq!(relation.topological_sort(&[3, 1, 2]), Ok(vec![1, 2, 3]));
+    assert!(relation.respects(&[1, 2, 3]));
+    assert!(!relation.respects(&[1, 3, 2]));
+    let mut sequence = vec![3, 2, 1];
+    sequence.sort_by(relation.comparator());
+    assert_eq!(sequence, vec![1, 2, 3]);
+}
+#[test]
+fn test_topological_sort_keeps_unrelated_order() {
+    let relation: Relation<char> = [('b', 'a')].into_iter().collect();
+    assert_eq!(
+        relation.topological_sort(&['c', 'a', 'b', 'd']),
+        Ok(vec!['c', 'b', 'd', 'a'])
+    );
+}
+#[test]
+fn test_topological_sort_repeated_elements() {
+    let relation: Relation<u32> = [(1, 2)].into_iter().collect();
+    assert_eq!(relation.topological_sort(&[1, 1]), Ok(vec![1]));
+    assert_eq!(relation.topological_sort(&[2, 1, 2]), Ok(vec![1, 2]));
+}
+#[test]
+fn test_find_cycle() {
+    let relation: Relation<u32> = [(1, 2), (2, 3), (3, 4), (4, 2)].into_iter().collect();
+    assert_eq!(relation.find_cycle(&[1, 2, 3]), None);
+    let cycle = relation.topological_sort(&[1, 2, 3, 4]).unwrap_err();
+    assert_eq!(cycle, Cycle(vec![2, 3, 4]));
+    assert_eq!(cycle.to_string(), "2 -> 3 -> 4 -> 2");
+}
@@ -0,0 +1,117 @@
+//! Data parallel helpers on top of [`std::thread::scope`].
+//!
+//! The input slice is split into one contiguous chunk per available thread, so the results keep
+//! the order of the input.
+
+use std::{iter::Sum, num::NonZeroUsize, thread};
+
+/// Returns the amount of threads the helpers split the work into.
+#[inline]
+pub fn thread_count() -> usize {
+    thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+/// Applies `f` to contiguous chunks of `items` on separate threads.
+///
+/// # Returns
+/// The result of every chunk in the order of the chunks. Empty input produces no chunk.
+pub fn par_map_chunks<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&[T]) -> R + Sync,
+{
+    if items.is_empty() {
+        return Vec::new();
+    }
+    let chunk_size = items.len().div_ceil(thread_count());
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || f(chunk)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("A worker thread panicked"))
+            .collect()
+    })
+}
+
+/// Like [`par_map_chunks`], but every thread first creates its own state with `init`, e.g. a
+/// simulation that is reset for every item instead of being allocated again.
+pub fn par_map_chunks_with<T, S, R, I, F>(items: &[T], init: I, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    I: Fn() -> S + Sync,
+    F: Fn(&mut S, &[T]) -> R + Sync,
+{
+    par_map_chunks(items, |chunk| f(&mut init(), chunk))
+}
+
+/// Maps every item on multiple threads, keeping the order of `items`.
+pub fn par_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    par_map_chunks(items, |chunk| chunk.iter().map(&f).collect::<Vec<_>>())
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+/// Counts the items matching `predicate` on multiple threads.
+pub fn par_filter_count<T, F>(items: &[T], predicate: F) -> usize
+where
+    T: Sync,
+    F: Fn(&T) -> bool + Sync,
+{
+    par_filter_count_with(items, || (), |_, item| predicate(item))
+}
+
+/// Like [`par_filter_count`] with a state per thread created by `init`.
+pub fn par_filter_count_with<T, S, I, F>(items: &[T], init: I, predicate: F) -> usize
+where
+    T: Sync,
+    I: Fn() -> S + Sync,
+    F: Fn(&mut S, &T) -> bool + Sync,
+{
+    par_map_chunks_with(items, init, |state, chunk| {
+        chunk.iter().filter(|item| predicate(state, item)).count()
+    })
+    .into_iter()
+    .sum()
+}
+
+/// Sums `f` over every item on multiple threads.
+pub fn par_sum<T, S, F>(items: &[T], f: F) -> S
+where
+    T: Sync,
+    S: Sum + Send,
+    F: Fn(&T) -> S + Sync,
+{
+    par_map_chunks(items, |chunk| chunk.iter().map(&f).sum::<S>())
+        .into_iter()
+        .sum()
+}
+
+#[test]
+fn test_par_helpers() {
+    let items: Vec<u64> = (0..1000).collect();
+    assert_eq!(
+        par_map(&items, |v| v * 2),
+        items.iter().map(|v| v * 2).collect::<Vec<_>>()
+    );
+    assert_eq!(par_filter_count(&items, |v| v % 3 == 0), 334);
+    assert_eq!(par_sum(&items, |&v| v), 499_500);
+    assert_eq!(par_sum(&[] as &[u64], |&v| v), 0);
+    assert!(par_map(&[] as &[u8], |&v| v).is_empty());
+    let counted = par_filter_count_with(&items, Vec::new, |seen, &v| {
+        seen.push(v);
+        seen.len() == 1
+    });
+    assert_eq!(counted, par_map_chunks(&items, |chunk| chunk.len()).len());
+}
@@ -0,0 +1,405 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    iter::Sum,
+    num::ZeroablePrimitive,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign},
+};
+
+use super::datatypes::num_wrapper::NumWrapper;
+
+/// Signed primitive integers that can be used as the numerator and denominator of a [`Rational`].
+pub trait SignedInteger:
+    ZeroablePrimitive
+    + From<i8>
+    + Copy
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+}
+impl<T> SignedInteger for T where
+    T: ZeroablePrimitive
+        + From<i8>
+        + Copy
+        + Ord
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>
+        + Neg<Output = T>
+{
+}
+
+#[inline]
+fn abs<T: SignedInteger>(value: T) -> T {
+    if value < 0i8.into() {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Returns the greatest common divisor of `a` and `b`, which is never negative.
+#[inline]
+pub fn gcd<T: SignedInteger>(a: T, b: T) -> T {
+    let (mut a, mut b) = (abs(a), abs(b));
+    while b != 0i8.into() {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// An exact fraction `numerator / denominator`.
+///
+/// The value is always kept normalized: the denominator is positive and shares no common factor
+/// with the numerator, so zero is always stored as `0/1`. This makes the derived equality and
+/// hashing agree with the mathematical value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational<T>
+where
+    T: SignedInteger,
+{
+    numerator: T,
+    denominator: T,
+}
+
+impl<T> Rational<T>
+where
+    T: SignedInteger,
+{
+    /// Creates the normalized fraction `numerator / denominator`.
+    ///
+    /// # Panics
+    /// If `denominator` is zero.
+    pub fn new(numerator: T, denominator: T) -> Self {
+        assert!(
+            denominator != 0i8.into(),
+            "The denominator must not be zero"
+        );
+        let divisor = gcd(numerator, denominator);
+        let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+        if denominator < 0i8.into() {
+            Self {
+                numerator: -numerator,
+                denominator: -denominator,
+            }
+        } else {
+            Self {
+                numerator,
+                denominator,
+            }
+        }
+    }
+    #[inline]
+    pub fn from_integer(value: T) -> Self {
+        Self {
+            numerator: value,
+            denominator: 1i8.into(),
+        }
+    }
+    #[inline]
+    pub fn zero() -> Self {
+        Self::from_integer(0i8.into())
+    }
+    #[inline]
+    pub fn one() -> Self {
+        Self::from_integer(1i8.into())
+    }
+    #[inline]
+    pub fn numerator(&self) -> T {
+        self.numerator
+    }
+    #[inline]
+    pub fn denominator(&self) -> T {
+        self.denominator
+    }
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.numerator == 0i8.into()
+    }
+    #[inline]
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1i8.into()
+    }
+    /// Returns the value as an integer if the fraction has no remainder.
+    #[inline]
+    pub fn to_integer(self) -> Option<T> {
+        self.is_integer().then_some(self.numerator)
+    }
+    #[inline]
+    pub fn abs(&self) -> Self {
+        Self {
+            numerator: abs(self.numerator),
+            denominator: self.denominator,
+        }
+    }
+    /// Returns `1 / self`.
+    ///
+    /// # Panics
+    /// If `self` is zero.
+    #[inline]
+    pub fn recip(&self) -> Self {
+        Self::new(self.denominator, self.numerator)
+    }
+}
+
+impl<T> From<T> for Rational<T>
+where
+    T: SignedInteger,
+{
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::from_integer(value)
+    }
+}
+
+impl<T> Display for Rational<T>
+where
+    T: SignedInteger + Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            f.write_fmt(format_args!("{}", self.numerator))
+        } else {
+            f.write_fmt(format_args!("{}/{}", self.numerator, self.denominator))
+        }
+    }
+}
+
+impl<T> PartialOrd for Rational<T>
+where
+    T: SignedInteger,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<T> Ord for Rational<T>
+where
+    T: SignedInteger,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.denominator == other.denominator {
+            return self.numerator.cmp(&other.numerator);
+        }
+        let divisor = gcd(self.denominator, other.denominator);
+        (self.numerator * (other.denominator / divisor))
+            .cmp(&(other.numerator * (self.denominator / divisor)))
+    }
+}
+
+impl<T> Neg for Rational<T>
+where
+    T: SignedInteger,
+{
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+impl<T> Add for Rational<T>
+where
+    T: SignedInteger,
+{
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let divisor = gcd(self.denominator, rhs.denominator);
+        let lhs_factor = rhs.denominator / divisor;
+        let rhs_factor = self.denominator / divisor;
+        Self::new(
+            self.numerator * lhs_factor + rhs.numerator * rhs_factor,
+            self.denominator * lhs_factor,
+        )
+    }
+}
+impl<T> Sub for Rational<T>
+where
+    T: SignedInteger,
+{
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+impl<T> Mul for Rational<T>
+where
+    T: SignedInteger,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        // cross reducing first keeps the intermediate products small
+        let divisor_1 = gcd(self.numerator, rhs.denominator);
+        let divisor_2 = gcd(rhs.numerator, self.denominator);
+        Self::new(
+            (self.numerator / divisor_1) * (rhs.numerator / divisor_2),
+            (self.denominator / divisor_2) * (rhs.denominator / divisor_1),
+        )
+    }
+}
+impl<T> Div for Rational<T>
+where
+    T: SignedInteger,
+{
+    type Output = Self;
+
+    /// # Panics
+    /// If `rhs` is zero.
+    fn div(self, rhs: Self) -> Self::Output {
+        assert!(!rhs.is_zero(), "Attempt to divide by a zero rational");
+        let divisor_1 = gcd(self.numerator, rhs.numerator);
+        let divisor_2 = gcd(self.denominator, rhs.denominator);
+        Self::new(
+            (self.numerator / divisor_1) * (rhs.denominator / divisor_2),
+            (self.denominator / divisor_2) * (rhs.numerator / divisor_1),
+        )
+    }
+}
+impl<T> Rem for Rational<T>
+where
+    T: SignedInteger,
+{
+    type Output = Self;
+
+    /// Returns the remainder of the truncated division, so the result has the sign of `self`.
+    ///
+    /// # Panics
+    /// If `rhs` is zero.
+    fn rem(self, rhs: Self) -> Self::Output {
+        let quotient = self / rhs;
+        let truncated = Self::from_integer(quotient.numerator / quotient.denominator);
+        self - rhs * truncated
+    }
+}
+
+macro_rules! rational_ops_with_integer {
+    ($($trait:ident::$function:ident, $assign_trait:ident::$assign_function:ident);+) => {
+        $(
+        impl<T> $trait<T> for Rational<T>
+        where
+            T: SignedInteger,
+        {
+            type Output = Self;
+
+            #[inline]
+            fn $function(self, rhs: T) -> Self::Output {
+                self.$function(Self::from_integer(rhs))
+            }
+        }
+        impl<T> $assign_trait for Rational<T>
+        where
+            T: SignedInteger,
+        {
+            #[inline]
+            fn $assign_function(&mut self, rhs: Self) {
+                *self = (*self).$function(rhs);
+            }
+        }
+        impl<T> $assign_trait<T> for Rational<T>
+        where
+            T: SignedInteger,
+        {
+            #[inline]
+            fn $assign_function(&mut self, rhs: T) {
+                *self = (*self).$function(Self::from_integer(rhs));
+            }
+        }
+        )+
+    };
+}
+rational_ops_with_integer!(
+    Add::add, AddAssign::add_assign;
+    Sub::sub, SubAssign::sub_assign;
+    Mul::mul, MulAssign::mul_assign;
+    Div::div, DivAssign::div_assign;
+    Rem::rem, RemAssign::rem_assign
+);
+
+impl<T> Sum for Rational<T>
+where
+    T: SignedInteger,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<T, P> AddAssign<Rational<T>> for NumWrapper<T, P>
+where
+    NumWrapper<T, P>: AddAssign<T>,
+    T: SignedInteger,
+{
+    /// Adds an integral rational to the accumulated value.
+    ///
+    /// # Panics
+    /// If `rhs` is not an integer, because the wrapper can not represent the fractional part.
+    #[inline]
+    fn add_assign(&mut self, rhs: Rational<T>) {
+        *self += rhs
+            .to_integer()
+            .expect("Only integral rationals can be added to a NumWrapper");
+    }
+}
+
+#[test]
+fn test_rational_normalization() {
+    let value = Rational::new(6i64, -4);
+    assert_eq!(value.numerator(), -3);
+    assert_eq!(value.denominator(), 2);
+    assert_eq!(Rational::new(0i64, -7), Rational::zero());
+    assert_eq!(Rational::new(10i64, 5).to_integer(), Some(2));
+    assert!(!value.is_integer());
+}
+#[test]
+fn test_rational_arithmetic() {
+    let a = Rational::new(1i64, 3);
+    let b = Rational::new(1i64, 6);
+    assert_eq!(a + b, Rational::new(1, 2));
+    assert_eq!(a - b, Rational::new(1, 6));
+    assert_eq!(a * b, Rational::new(1, 18));
+    assert_eq!(a / b, Rational::from(2));
+    assert_eq!(
+        Rational::new(7i64, 2) % Rational::from(2),
+        Rational::new(3, 2)
+    );
+    assert_eq!(
+        Rational::new(-7i64, 2) % Rational::from(2),
+        Rational::new(-3, 2)
+    );
+    let mut c = a;
+    c *= 3;
+    assert!(c.is_integer());
+    assert_eq!(c.to_string(), "1");
+    assert_eq!(b.to_string(), "1/6");
+}
+#[test]
+fn test_rational_ordering() {
+    assert!(Rational::new(1i32, 3) < Rational::new(1, 2));
+    assert!(Rational::new(-1i32, 2) < Rational::new(-1, 3));
+    assert_eq!(
+        [1, 2, 3].map(|d| Rational::new(1i32, d)).into_iter().max(),
+        Some(Rational::one())
+    );
+}
+#[test]
+fn test_rational_num_wrapper() {
+    let mut result: NumWrapper<i64> = NumWrapper::new(0);
+    result += Rational::new(4i64, 2);
+    result += Rational::from(3i64);
+    assert_eq!(*result, 5);
+}
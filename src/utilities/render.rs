@@ -0,0 +1,209 @@
+//! Dependency free rendering of grids into binary PPM and SVG images.
+//!
+//! A grid is described by its size and a palette function that returns the [`Color`] of every
+//! cell, so any representation of a grid can be rendered without converting it first.
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use super::grid::GridPosition;
+
+static OUTPUT_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Sets the directory the runner writes requested renderings into, see [`output_path`].
+pub fn set_output_dir(dir: impl Into<PathBuf>) -> std::io::Result<()> {
+    let dir = dir.into();
+    std::fs::create_dir_all(&dir)?;
+    *OUTPUT_DIR.lock().unwrap() = Some(dir);
+    Ok(())
+}
+/// Returns the path for the rendering `name` if the runner was asked to render, so days only
+/// build their images when somebody looks at them.
+pub fn output_path(name: &str) -> Option<PathBuf> {
+    OUTPUT_DIR
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|dir| dir.join(name))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const GRAY: Color = Color::rgb(96, 96, 96);
+    pub const RED: Color = Color::rgb(220, 50, 47);
+    pub const GREEN: Color = Color::rgb(46, 160, 67);
+
+    #[inline]
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+    /// Returns a color for the `index`th category, e.g. a region or an antenna frequency.
+    ///
+    /// The hues are spread with the golden angle, so neighbouring indices get clearly different
+    /// colors.
+    pub fn from_index(index: usize) -> Self {
+        let hue = (index as f64 * 137.507_764) % 360.0;
+        let sector = hue / 60.0;
+        let x = 1.0 - (sector % 2.0 - 1.0).abs();
+        let (r, g, b) = match sector as u32 {
+            0 => (1.0, x, 0.0),
+            1 => (x, 1.0, 0.0),
+            2 => (0.0, 1.0, x),
+            3 => (0.0, x, 1.0),
+            4 => (x, 0.0, 1.0),
+            _ => (1.0, 0.0, x),
+        };
+        let scale = |channel: f64| (55.0 + channel * 200.0) as u8;
+        Self::rgb(scale(r), scale(g), scale(b))
+    }
+    #[inline]
+    fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// Renders a `rows` x `columns` grid where every cell becomes a `scale` x `scale` square.
+pub struct GridRenderer<F> {
+    rows: usize,
+    columns: usize,
+    scale: usize,
+    palette: F,
+}
+
+impl<F> GridRenderer<F>
+where
+    F: Fn(GridPosition) -> Color,
+{
+    pub fn new(rows: usize, columns: usize, palette: F) -> Self {
+        Self {
+            rows,
+            columns,
+            scale: 4,
+            palette,
+        }
+    }
+    /// Sets the side length of a cell in pixels, which defaults to `4`.
+    pub fn scale(mut self, scale: usize) -> Self {
+        assert!(scale > 0, "A cell needs at least one pixel");
+        self.scale = scale;
+        self
+    }
+    /// Writes the grid as a binary (`P6`) PPM image.
+    pub fn write_ppm(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let (width, height) = (self.columns * self.scale, self.rows * self.scale);
+        write!(writer, "P6\n{width} {height}\n255\n")?;
+        let mut line = Vec::with_capacity(width * 3);
+        for row in 0..self.rows {
+            line.clear();
+            for column in 0..self.columns {
+                let color = (self.palette)((row, column));
+                for _ in 0..self.scale {
+                    line.extend([color.r, color.g, color.b]);
+                }
+            }
+            for _ in 0..self.scale {
+                writer.write_all(&line)?;
+            }
+        }
+        Ok(())
+    }
+    /// Writes the grid as an SVG image, merging horizontal runs of the same color into a single
+    /// rectangle to keep the file small.
+    pub fn write_svg(&self, mut writer: impl Write) -> std::io::Result<()> {
+        let (width, height) = (self.columns * self.scale, self.rows * self.scale);
+        writeln!(
+            writer,
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" "#,
+                r#"shape-rendering="crispEdges">"#
+            ),
+            width, height
+        )?;
+        for row in 0..self.rows {
+            let mut start = 0;
+            while start < self.columns {
+                let color = (self.palette)((row, start));
+                let mut end = start + 1;
+                while end < self.columns && (self.palette)((row, end)) == color {
+                    end += 1;
+                }
+                writeln!(
+                    writer,
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                    start * self.scale,
+                    row * self.scale,
+                    (end - start) * self.scale,
+                    self.scale,
+                    color.hex()
+                )?;
+                start = end;
+            }
+        }
+        writeln!(writer, "</svg>")
+    }
+    /// Writes the image to `path`, choosing SVG for a `.svg` extension and PPM otherwise.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let path = path.as_ref();
+        let writer = BufWriter::new(File::create(path)?);
+        match path.extension().is_some_and(|extension| extension == "svg") {
+            true => self.write_svg(writer),
+            false => self.write_ppm(writer),
+        }
+    }
+    /// Saves the image as `name` into the output directory, if the runner was asked to render.
+    pub fn save_if_requested(&self, name: &str) {
+        if let Some(path) = output_path(name) {
+            self.save(&path)
+                .unwrap_or_else(|err| panic!("Could not save {}: {err}", path.display()));
+        }
+    }
+}
+
+#[test]
+fn test_write_ppm() {
+    let renderer = GridRenderer::new(1, 2, |(_, column)| match column {
+        0 => Color::BLACK,
+        _ => Color::rgb(1, 2, 3),
+    })
+    .scale(2);
+    let mut image = Vec::new();
+    renderer.write_ppm(&mut image).unwrap();
+    let mut expected = b"P6\n4 2\n255\n".to_vec();
+    for _ in 0..2 {
+        expected.extend([0, 0, 0, 0, 0, 0, 1, 2, 3, 1, 2, 3]);
+    }
+    assert_eq!(image, expected);
+}
+#[test]
+fn test_write_svg() {
+    let grid = ["##.", "..."];
+    let renderer = GridRenderer::new(2, 3, |(row, column)| match grid[row].as_bytes()[column] {
+        b'#' => Color::WHITE,
+        _ => Color::BLACK,
+    })
+    .scale(1);
+    let mut image = Vec::new();
+    renderer.write_svg(&mut image).unwrap();
+    let image = String::from_utf8(image).unwrap();
+    assert_eq!(image.matches("<rect").count(), 3);
+    assert!(image.contains(r##"<rect x="0" y="0" width="2" height="1" fill="#ffffff"/>"##));
+    assert!(image.contains(r##"<rect x="0" y="1" width="3" height="1" fill="#000000"/>"##));
+    assert!(image.trim_end().ends_with("</svg>"));
+}
+#[test]
+fn test_color_from_index() {
+    assert_eq!(Color::from_index(0), Color::rgb(255, 55, 55));
+    assert_ne!(Color::from_index(1), Color::from_index(2));
+    assert_eq!(Color::rgb(255, 0, 16).hex(), "#ff0010");
+}
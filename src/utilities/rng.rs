@@ -0,0 +1,94 @@
+//! A small seeded pseudo random number generator for synthetic inputs.
+//!
+//! It is not suited for cryptography, but the same seed yields the same numbers on every platform
+//! and compiler version, which keeps generated inputs reproducible.
+
+use std::ops::Range;
+
+/// The SplitMix64 generator, which passes BigCrush with a single `u64` of state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+impl Rng {
+    #[inline]
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+    /// Returns a number below `bound` without modulo bias.
+    ///
+    /// # Panics
+    /// If `bound` is `0`.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "The bound has to be positive");
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let value = self.next_u64();
+            if value < zone {
+                return value % bound;
+            }
+        }
+    }
+    /// Returns a number in `range`.
+    ///
+    /// # Panics
+    /// If `range` is empty.
+    #[inline]
+    pub fn range(&mut self, range: Range<usize>) -> usize {
+        assert!(
+            !range.is_empty(),
+            "Can not pick from the empty range {range:?}"
+        );
+        range.start + self.below((range.end - range.start) as u64) as usize
+    }
+    /// Returns a number in `[0, 1)`.
+    #[inline]
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+    /// Returns `true` with the given `probability`.
+    #[inline]
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+    /// Returns a random element.
+    ///
+    /// # Panics
+    /// If `values` is empty.
+    #[inline]
+    pub fn choose<'a, T>(&mut self, values: &'a [T]) -> &'a T {
+        &values[self.range(0..values.len())]
+    }
+    /// Shuffles `values` in place with the Fisher-Yates algorithm.
+    pub fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            values.swap(i, self.range(0..i + 1));
+        }
+    }
+}
+
+#[test]
+fn test_rng() {
+    let mut rng = Rng::new(1234567);
+    assert_eq!(rng.next_u64(), 6457827717110365317);
+    assert_eq!(rng.next_u64(), 3203168211198807973);
+    let mut a = Rng::new(42);
+    let mut b = Rng::new(42);
+    assert!((0..100).all(|_| a.range(3..17) == b.range(3..17)));
+    assert!((0..1000)
+        .map(|_| a.range(3..17))
+        .all(|v| (3..17).contains(&v)));
+    let mut values: Vec<u32> = (0..20).collect();
+    rng.shuffle(&mut values);
+    assert_ne!(values, (0..20).collect::<Vec<_>>());
+    values.sort();
+    assert_eq!(values, (0..20).collect::<Vec<_>>());
+}
@@ -0,0 +1,360 @@
+//! Declarative descriptions of the puzzle inputs.
+//!
+//! Every day declares the shape of its input as a [`Schema`]. Validating a file against it
+//! explains what is wrong with the file, e.g. a grid of the wrong size or an HTML error page saved
+//! as `input.txt`, before a solution panics somewhere in the middle of solving.
+
+use std::{fmt::Display, ops::RangeInclusive};
+
+use super::input::Input;
+
+/// The characters allowed in a grid or a line.
+#[derive(Clone, Copy, Debug)]
+pub enum Alphabet {
+    /// Exactly the characters of the string.
+    Chars(&'static str),
+    /// The characters matching the predicate, described by the name.
+    Class(&'static str, fn(&char) -> bool),
+}
+impl Alphabet {
+    /// Letters, digits, punctuation and spaces.
+    pub const PRINTABLE: Self =
+        Self::Class("printable ASCII", |c| *c == ' ' || c.is_ascii_graphic());
+    pub const DIGITS: Self = Self::Class("digits", char::is_ascii_digit);
+
+    pub fn contains(&self, c: char) -> bool {
+        match self {
+            Self::Chars(chars) => chars.contains(c),
+            Self::Class(_, predicate) => predicate(&c),
+        }
+    }
+}
+impl Display for Alphabet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Chars(chars) => f.write_fmt(format_args!("one of {chars:?}")),
+            Self::Class(name, _) => f.write_str(name),
+        }
+    }
+}
+
+/// The dimensions of a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GridSize {
+    /// As many rows as columns.
+    Square,
+    /// Exactly `rows` x `columns`.
+    Exactly(usize, usize),
+}
+
+/// The shape of a single line.
+#[derive(Clone, Debug)]
+pub enum LineShape {
+    /// Unsigned integers separated by `separator`, where `" "` allows any run of whitespace.
+    Numbers {
+        separator: &'static str,
+        count: RangeInclusive<usize>,
+    },
+    /// Any amount of characters of the alphabet, e.g. the digits of a disk map.
+    Chars(Alphabet),
+    /// The parts around the first `separator`, each with its own shape, e.g. `190: 10 19`.
+    Split {
+        separator: &'static str,
+        head: &'static LineShape,
+        tail: &'static LineShape,
+    },
+}
+impl LineShape {
+    fn check(&self, line: &str) -> Result<(), String> {
+        match self {
+            Self::Numbers { separator, count } => {
+                let numbers: Vec<&str> = match *separator {
+                    " " => line.split_whitespace().collect(),
+                    _ => line.split(separator).map(str::trim).collect(),
+                };
+                let invalid = numbers
+                    .iter()
+                    .find(|n| n.is_empty() || !n.bytes().all(|b| b.is_ascii_digit()));
+                if let Some(invalid) = invalid {
+                    return Err(format!("expected a number but found {invalid:?}"));
+                }
+                if !count.contains(&numbers.len()) {
+                    let expected = describe_count(count, "number");
+                    return Err(format!("expected {expected} but found {}", numbers.len()));
+                }
+                Ok(())
+            }
+            Self::Chars(alphabet) => match line.chars().position(|c| !alphabet.contains(c)) {
+                Some(column) => {
+                    let c = line.chars().nth(column).unwrap_or_default();
+                    Err(format!(
+                        "unexpected {c:?} in column {}, expected {alphabet}",
+                        column + 1
+                    ))
+                }
+                None => Ok(()),
+            },
+            Self::Split {
+                separator,
+                head,
+                tail,
+            } => {
+                let (before, after) = line
+                    .split_once(separator)
+                    .ok_or_else(|| format!("missing the separator {separator:?}"))?;
+                head.check(before)?;
+                tail.check(after)
+            }
+        }
+    }
+}
+
+/// The shape of a whole input.
+#[derive(Clone, Debug)]
+pub enum Schema {
+    /// Lines of the same shape, with the amount of them in `count`.
+    Lines {
+        shape: LineShape,
+        count: RangeInclusive<usize>,
+    },
+    /// A rectangular grid of characters.
+    Grid { size: GridSize, alphabet: Alphabet },
+    /// Blocks separated by blank lines, each with its own schema.
+    Sections(&'static [Schema]),
+}
+impl Schema {
+    /// Checks `input` against the schema.
+    ///
+    /// # Returns
+    /// Every part of the input that does not fit. Mismatches of the whole input, like the size of a
+    /// grid, come first and the ones of single lines follow in input order. Files that are not
+    /// puzzle inputs at all, like an empty file or an HTML page, only yield a single mismatch.
+    pub fn validate(&self, input: &str) -> Vec<Mismatch> {
+        if let Some(mismatch) = check_document(input) {
+            return vec![mismatch];
+        }
+        let lines: Vec<(usize, &str)> = input.numbered_lines().collect();
+        let mut mismatches = Vec::new();
+        self.validate_lines(&lines, &mut mismatches);
+        mismatches.sort_by_key(|mismatch| mismatch.line.is_some());
+        mismatches
+    }
+    fn validate_lines(&self, lines: &[(usize, &str)], mismatches: &mut Vec<Mismatch>) {
+        match self {
+            Self::Lines { shape, count } => {
+                for &(line, s) in lines {
+                    if let Err(message) = shape.check(s) {
+                        mismatches.push(Mismatch::new(Some(line), message));
+                    }
+                }
+                if !count.contains(&lines.len()) {
+                    let expected = describe_count(count, "line");
+                    let found = lines.len();
+                    mismatches.push(Mismatch::new(
+                        None,
+                        format!("expected {expected} but found {found}"),
+                    ));
+                }
+            }
+            Self::Grid { size, alphabet } => {
+                let columns = lines.first().map_or(0, |(_, s)| s.chars().count());
+                for &(line, s) in lines {
+                    let found = s.chars().count();
+                    if found != columns {
+                        let message = format!(
+                            "expected {columns} columns like the first row but found {found}"
+                        );
+                        mismatches.push(Mismatch::new(Some(line), message));
+                    }
+                    if let Err(message) = LineShape::Chars(*alphabet).check(s) {
+                        mismatches.push(Mismatch::new(Some(line), message));
+                    }
+                }
+                let rows = lines.len();
+                let message = match *size {
+                    GridSize::Square if rows != columns => {
+                        format!("expected a square grid but found {rows}x{columns}")
+                    }
+                    GridSize::Exactly(r, c) if (rows, columns) != (r, c) => {
+                        format!("expected a {r}x{c} grid but found {rows}x{columns}")
+                    }
+                    _ => return,
+                };
+                mismatches.push(Mismatch::new(None, message));
+            }
+            Self::Sections(schemas) => {
+                let sections: Vec<&[(usize, &str)]> = lines
+                    .split(|(_, s)| s.trim().is_empty())
+                    .filter(|section| !section.is_empty())
+                    .collect();
+                for (schema, section) in schemas.iter().zip(&sections) {
+                    schema.validate_lines(section, mismatches);
+                }
+                if sections.len() != schemas.len() {
+                    let message = format!(
+                        "expected {} sections separated by blank lines but found {}",
+                        schemas.len(),
+                        sections.len()
+                    );
+                    mismatches.push(Mismatch::new(None, message));
+                }
+            }
+        }
+    }
+}
+
+/// Recognizes files that are not puzzle inputs at all.
+fn check_document(input: &str) -> Option<Mismatch> {
+    let start = input
+        .trim_start()
+        .get(..64)
+        .unwrap_or(input.trim_start())
+        .to_lowercase();
+    let message = if input.trim().is_empty() {
+        "the input is empty"
+    } else if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        "the input is an HTML page, e.g. an error page that was saved instead of the puzzle input"
+    } else if input.contains("Please log in to get your puzzle input") {
+        "the input asks to log in, the SESSION token is missing or expired"
+    } else {
+        return None;
+    };
+    Some(Mismatch::new(None, message.to_string()))
+}
+fn describe_count(count: &RangeInclusive<usize>, thing: &str) -> String {
+    let plural = |n: usize| {
+        if n == 1 {
+            thing.to_string()
+        } else {
+            format!("{thing}s")
+        }
+    };
+    match (*count.start(), *count.end()) {
+        (start, end) if start == end => format!("{start} {}", plural(start)),
+        (start, usize::MAX) => format!("at least {start} {}", plural(start)),
+        (start, end) => format!("{start} to {end} {}", plural(end)),
+    }
+}
+
+/// A part of an input that does not fit its schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// The line starting at `1`, if the mismatch is about a single line.
+    pub line: Option<usize>,
+    pub message: String,
+}
+impl Mismatch {
+    fn new(line: Option<usize>, message: String) -> Self {
+        Self { line, message }
+    }
+}
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => f.write_fmt(format_args!("line {line}: {}", self.message)),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+#[test]
+fn test_lines() {
+    const PAIR: Schema = Schema::Lines {
+        shape: LineShape::Numbers {
+            separator: " ",
+            count: 2..=2,
+        },
+        count: 1..=usize::MAX,
+    };
+    assert_eq!(PAIR.validate("3   4\n4   3\n"), vec![]);
+    let mismatches = PAIR.validate("3   4\n4\n5 x\n");
+    let messages: Vec<String> = mismatches.iter().map(Mismatch::to_string).collect();
+    assert_eq!(
+        messages,
+        [
+            "line 2: expected 2 numbers but found 1",
+            "line 3: expected a number but found \"x\""
+        ]
+    );
+    const EQUATION: Schema = Schema::Lines {
+        shape: LineShape::Split {
+            separator: ": ",
+            head: &LineShape::Numbers {
+                separator: " ",
+                count: 1..=1,
+            },
+            tail: &LineShape::Numbers {
+                separator: " ",
+                count: 1..=usize::MAX,
+            },
+        },
+        count: 1..=usize::MAX,
+    };
+    assert_eq!(EQUATION.validate("190: 10 19\n3267: 81 40 27"), vec![]);
+    assert_eq!(
+        EQUATION.validate("190 10 19")[0].message,
+        "missing the separator \": \""
+    );
+}
+#[test]
+fn test_grid() {
+    const GRID: Schema = Schema::Grid {
+        size: GridSize::Square,
+        alphabet: Alphabet::Chars(".#^"),
+    };
+    assert_eq!(GRID.validate("..#\n.^.\n#..\n"), vec![]);
+    let messages: Vec<String> = GRID
+        .validate("..#\n.^.x\n")
+        .iter()
+        .map(Mismatch::to_string)
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            "expected a square grid but found 2x3",
+            "line 2: expected 3 columns like the first row but found 4",
+            "line 2: unexpected 'x' in column 4, expected one of \".#^\""
+        ]
+    );
+    let fixed = Schema::Grid {
+        size: GridSize::Exactly(2, 2),
+        alphabet: Alphabet::DIGITS,
+    };
+    assert_eq!(fixed.validate("12\n34").len(), 0);
+    assert_eq!(fixed.validate("123\n456\n789").len(), 1);
+}
+#[test]
+fn test_sections_and_documents() {
+    const RULES_AND_UPDATES: Schema = Schema::Sections(&[
+        Schema::Lines {
+            shape: LineShape::Numbers {
+                separator: "|",
+                count: 2..=2,
+            },
+            count: 1..=usize::MAX,
+        },
+        Schema::Lines {
+            shape: LineShape::Numbers {
+                separator: ",",
+                count: 1..=usize::MAX,
+            },
+            count: 1..=usize::MAX,
+        },
+    ]);
+    assert_eq!(
+        RULES_AND_UPDATES.validate("47|53\n97|13\n\n75,47,61\n"),
+        vec![]
+    );
+    let mismatches = RULES_AND_UPDATES.validate("47|53\n97|13\n75,47,61\n");
+    assert_eq!(
+        mismatches[0].message,
+        "expected 2 sections separated by blank lines but found 1"
+    );
+    assert_eq!(mismatches[1].line, Some(3));
+    let html = "\n<!DOCTYPE html>\n<html lang=\"en-us\">\n<head>\n";
+    assert_eq!(RULES_AND_UPDATES.validate(html).len(), 1);
+    assert_eq!(
+        RULES_AND_UPDATES.validate(" \n")[0].message,
+        "the input is empty"
+    );
+}
@@ -0,0 +1,53 @@
+//! Verbosity controlled output for intermediate puzzle state, see the [`trace!`](crate::trace)
+//! macro.
+//!
+//! Traces go to stderr, or to a trace file once one is set, so they never mix with the answers
+//! printed to stdout.
+
+use std::{
+    fmt::Arguments,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Mutex,
+    },
+};
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+static TRACE_FILE: Mutex<Option<BufWriter<File>>> = Mutex::new(None);
+
+#[inline]
+pub fn level() -> u8 {
+    LEVEL.load(Ordering::Relaxed)
+}
+#[inline]
+pub fn set_level(level: u8) {
+    LEVEL.store(level, Ordering::Relaxed);
+}
+/// Returns `true` if traces of `level` are written, which needs a verbosity of at least `level`.
+#[inline]
+pub fn enabled(level: u8) -> bool {
+    level <= self::level()
+}
+/// Redirects all following traces into a newly created file at `path`.
+pub fn set_trace_file(path: impl AsRef<Path>) -> std::io::Result<()> {
+    let file = File::create(path)?;
+    *TRACE_FILE.lock().unwrap() = Some(BufWriter::new(file));
+    Ok(())
+}
+/// Writes the buffered traces into the trace file.
+pub fn flush() -> std::io::Result<()> {
+    match TRACE_FILE.lock().unwrap().as_mut() {
+        Some(file) => file.flush(),
+        None => Ok(()),
+    }
+}
+/// Writes a single trace line, independent of the verbosity.
+pub fn write(args: Arguments) {
+    match TRACE_FILE.lock().unwrap().as_mut() {
+        Some(file) => writeln!(file, "{args}").expect("Could not write to the trace file"),
+        None => eprintln!("{args}"),
+    }
+}